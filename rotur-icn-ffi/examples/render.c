@@ -0,0 +1,52 @@
+/*
+ * Render an ICN from stdin into a PAM on stdout, fitted at 10 pixels per unit
+ *
+ * cargo build --release -p rotur-icn-ffi
+ * cc rotur-icn-ffi/examples/render.c -Irotur-icn-ffi/include -Ltarget/release -lrotur_icn_ffi -o render
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "rotur_icn.h"
+
+int main(void) {
+    static char src[1 << 16];
+    size_t len = fread(src, 1, sizeof src, stdin);
+
+    RoturIcn *icon = NULL;
+    if (rotur_icn_process(src, len, &icon) != ROTUR_ICN_OK) {
+        fputs("failed to process the ICN\n", stderr);
+        return 1;
+    }
+
+    for (size_t i = 0; i < rotur_icn_error_count(icon); i++) {
+        fprintf(stderr, "%s\n", rotur_icn_error_message(icon, i));
+    }
+
+    RoturIcnView view = { .scale = 10.0f, .background = 0x00000000 };
+    size_t width, height;
+
+    if (rotur_icn_fit(icon, &view) != ROTUR_ICN_OK
+        || rotur_icn_render_size(&view, &width, &height) != ROTUR_ICN_OK) {
+        rotur_icn_free(icon);
+        return 1;
+    }
+
+    size_t buf_len = width * height * 4;
+    uint8_t *buf = malloc(buf_len);
+
+    RoturIcnStatus status = rotur_icn_render(icon, &view, buf, buf_len);
+    rotur_icn_free(icon);
+
+    if (status != ROTUR_ICN_OK) {
+        free(buf);
+        return 1;
+    }
+
+    printf("P7\nWIDTH %zu\nHEIGHT %zu\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n", width, height);
+    fwrite(buf, 1, buf_len, stdout);
+    free(buf);
+
+    return 0;
+}
@@ -0,0 +1,89 @@
+#ifndef ROTUR_ICN_H
+#define ROTUR_ICN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Ownership:
+ * - a `RoturIcn` is owned by the caller from `rotur_icn_process` until `rotur_icn_free`
+ * - error messages are owned by their `RoturIcn` & live as long as it does
+ * - buffers to render into are owned by the caller
+ *
+ * No panic crosses the boundary, functions returning a status report them as
+ * `ROTUR_ICN_PANICKED`. Null pointers are reported as `ROTUR_ICN_NULL_POINTER`.
+ */
+
+typedef enum RoturIcnStatus {
+    ROTUR_ICN_OK = 0,
+    ROTUR_ICN_NULL_POINTER = 1,
+    ROTUR_ICN_INVALID_UTF8 = 2,
+    ROTUR_ICN_WRONG_BUFFER_SIZE = 3,
+    ROTUR_ICN_PANICKED = 4,
+    ROTUR_ICN_CANVAS_TOO_LARGE = 5,
+    ROTUR_ICN_NON_FINITE_PARAMETER = 6,
+} RoturIcnStatus;
+
+/* A processed ICN */
+typedef struct RoturIcn RoturIcn;
+
+/* The canvas to render onto, in ICN units, & how */
+typedef struct RoturIcnView {
+    float width;
+    float height;
+    /* pixels per unit */
+    float scale;
+    float camera_x;
+    float camera_y;
+    /* RGBA, as 0xRRGGBBAA */
+    uint32_t background;
+    /* pixels the canvas may have at most, 0 for the default */
+    size_t pixel_budget;
+} RoturIcnView;
+
+/*
+ * Process the UTF-8 source of `len` bytes, which needn't be NUL-terminated,
+ * into a new ICN stored in `out`.
+ * An ICN with errors is still made, holding as much as was understood.
+ * On failure, `out` is left alone.
+ */
+RoturIcnStatus rotur_icn_process(const char *src, size_t len, RoturIcn **out);
+
+/* Free the ICN, after which neither it nor its messages may be used. Null is ignored. */
+void rotur_icn_free(RoturIcn *icon);
+
+/* Number of the errors found while processing the ICN, 0 for null */
+size_t rotur_icn_error_count(const RoturIcn *icon);
+
+/* The NUL-terminated message of the error at `index`, null for null or an index out of bounds */
+const char *rotur_icn_error_message(const RoturIcn *icon, size_t index);
+
+/* Fit the view's canvas & camera around the ICN, leaving its scale & background alone */
+RoturIcnStatus rotur_icn_fit(const RoturIcn *icon, RoturIcnView *view);
+
+/*
+ * Store the size in pixels the view renders to, the buffer holds 4 bytes per pixel.
+ * A canvas over the pixel budget or with parameters, which aren't finite, is reported.
+ */
+RoturIcnStatus rotur_icn_render_size(const RoturIcnView *view, size_t *width, size_t *height);
+
+/*
+ * Render the ICN into `buf` of straight RGBA pixels, row by row from the top.
+ * `len` has to be exactly `width * height * 4`, as told by `rotur_icn_render_size`.
+ */
+RoturIcnStatus rotur_icn_render(
+    const RoturIcn *icon,
+    const RoturIcnView *view,
+    uint8_t *buf,
+    size_t len
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
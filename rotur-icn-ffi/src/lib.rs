@@ -0,0 +1,307 @@
+//! The C ABI, declared in `include/rotur_icn.h`
+//!
+//! Every function catches panics, reporting them as [`RoturIcnStatus::Panicked`] where it can.
+
+use std::{
+    ffi::{CString, c_char},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+use rotur_icn_renderer::{
+    cpu::{RenderError, Renderer},
+    fitter,
+};
+use rotur_icn_resolver::lir;
+use rotur_icn_units::{Colour, Number, Vector};
+
+/// A processed ICN, owned by the caller from [`rotur_icn_process`] until [`rotur_icn_free`]
+pub struct RoturIcn {
+    icon: lir::IconLir,
+    /// Messages of the errors, in the order of the stages
+    messages: Vec<CString>,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoturIcnStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    WrongBufferSize = 3,
+    Panicked = 4,
+    CanvasTooLarge = 5,
+    NonFiniteParameter = 6,
+}
+
+impl RoturIcnStatus {
+    fn of_render_error(err: &RenderError) -> Self {
+        match err {
+            RenderError::NoIconLoaded => unreachable!("the ICN is loaded before rendering"),
+            RenderError::BadBufferSize { .. } => Self::WrongBufferSize,
+            RenderError::CanvasTooLarge { .. } => Self::CanvasTooLarge,
+            RenderError::NonFiniteParameter(_) => Self::NonFiniteParameter,
+        }
+    }
+}
+
+/// The canvas to render onto, in ICN units, & how
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoturIcnView {
+    pub width: Number,
+    pub height: Number,
+    /// Pixels per unit
+    pub scale: Number,
+    pub camera_x: Number,
+    pub camera_y: Number,
+    /// RGBA, as `0xRRGGBBAA`
+    pub background: u32,
+    /// Pixels the canvas may have at most, 0 for the default
+    pub pixel_budget: usize,
+}
+
+impl RoturIcnView {
+    fn renderer(&self) -> Renderer {
+        let mut renderer = Renderer::new(
+            Vector {
+                x: self.width,
+                y: self.height,
+            },
+            self.scale,
+            Vector {
+                x: self.camera_x,
+                y: self.camera_y,
+            },
+            Colour::from_u32_with_alpha(self.background),
+        );
+
+        if self.pixel_budget != 0 {
+            renderer.pixel_budget = self.pixel_budget;
+        }
+
+        renderer
+    }
+}
+
+fn guard(f: impl FnOnce() -> RoturIcnStatus) -> RoturIcnStatus {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(RoturIcnStatus::Panicked)
+}
+
+/// Process the UTF-8 source, which needn't be NUL-terminated, into a new ICN stored in `out`
+///
+/// An ICN with errors is still made, holding as much as was understood.
+/// On failure, `out` is left alone.
+///
+/// # Safety
+///
+/// `src` must point to `len` readable bytes & `out` to a writable pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rotur_icn_process(
+    src: *const c_char,
+    len: usize,
+    out: *mut *mut RoturIcn,
+) -> RoturIcnStatus {
+    guard(|| {
+        if src.is_null() || out.is_null() {
+            return RoturIcnStatus::NullPointer;
+        }
+
+        // SAFETY: the pointer isn't null, the caller guarantees the length
+        let src = unsafe { std::slice::from_raw_parts(src.cast::<u8>(), len) };
+
+        let Ok(src) = std::str::from_utf8(src) else {
+            return RoturIcnStatus::InvalidUtf8;
+        };
+
+        let (_, _, icon, errors) = rotur_icn_pipeline::process(src);
+
+        let messages = errors
+            .lexing
+            .iter()
+            .map(ToString::to_string)
+            .chain(errors.parsing.iter().map(ToString::to_string))
+            .chain(errors.lowering.iter().map(ToString::to_string))
+            .chain(errors.resolving.iter().map(ToString::to_string))
+            .chain(
+                errors
+                    .linting
+                    .iter()
+                    .filter(|warning| warning.is_denied())
+                    .map(ToString::to_string),
+            )
+            .map(|message| {
+                CString::new(message.replace('\0', "\u{fffd}"))
+                    .unwrap_or_else(|_| unreachable!("the NULs are replaced"))
+            })
+            .collect();
+
+        let icon = Box::new(RoturIcn { icon, messages });
+
+        // SAFETY: the pointer isn't null, the caller guarantees it's writable
+        unsafe { out.write(Box::into_raw(icon)) };
+
+        RoturIcnStatus::Ok
+    })
+}
+
+/// Free the ICN, after which neither it nor its messages may be used
+///
+/// Nothing happens for a null pointer.
+///
+/// # Safety
+///
+/// `icon` must be null or made by [`rotur_icn_process`] & not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rotur_icn_free(icon: *mut RoturIcn) {
+    if !icon.is_null() {
+        // SAFETY: the caller guarantees it's owned & made by a box
+        let icon = unsafe { Box::from_raw(icon) };
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(icon)));
+    }
+}
+
+/// Number of the errors found while processing the ICN, 0 for a null pointer
+///
+/// # Safety
+///
+/// `icon` must be null or a live ICN.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rotur_icn_error_count(icon: *const RoturIcn) -> usize {
+    // SAFETY: the caller guarantees it's null or live
+    unsafe { icon.as_ref() }.map_or(0, |icon| icon.messages.len())
+}
+
+/// The NUL-terminated message of the error at `index`,
+/// or null for a null pointer or an index out of bounds
+///
+/// The message is owned by the ICN & lives as long as it does.
+///
+/// # Safety
+///
+/// `icon` must be null or a live ICN.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rotur_icn_error_message(
+    icon: *const RoturIcn,
+    index: usize,
+) -> *const c_char {
+    // SAFETY: the caller guarantees it's null or live
+    unsafe { icon.as_ref() }
+        .and_then(|icon| icon.messages.get(index))
+        .map_or(ptr::null(), |message| message.as_ptr())
+}
+
+/// Fit the view's canvas & camera around the ICN, leaving its scale & background alone
+///
+/// # Safety
+///
+/// `icon` must be null or a live ICN & `view` null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rotur_icn_fit(
+    icon: *const RoturIcn,
+    view: *mut RoturIcnView,
+) -> RoturIcnStatus {
+    guard(|| {
+        // SAFETY: the caller guarantees they're null or valid
+        let (Some(icon), Some(view)) = (unsafe { icon.as_ref() }, unsafe { view.as_mut() }) else {
+            return RoturIcnStatus::NullPointer;
+        };
+
+        let fitted = fitter::fit(&icon.icon);
+
+        view.width = fitted.size.x;
+        view.height = fitted.size.y;
+        view.camera_x = fitted.camera.x;
+        view.camera_y = fitted.camera.y;
+
+        RoturIcnStatus::Ok
+    })
+}
+
+/// Store the size in pixels the view renders to in `width` & `height`
+///
+/// The buffer to render into holds 4 bytes per pixel.
+/// A canvas over the pixel budget or with parameters, which aren't finite, is reported.
+///
+/// # Safety
+///
+/// `view` must be null or readable, `width` & `height` null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rotur_icn_render_size(
+    view: *const RoturIcnView,
+    width: *mut usize,
+    height: *mut usize,
+) -> RoturIcnStatus {
+    guard(|| {
+        if width.is_null() || height.is_null() {
+            return RoturIcnStatus::NullPointer;
+        }
+
+        // SAFETY: the caller guarantees it's null or readable
+        let Some(view) = (unsafe { view.as_ref() }) else {
+            return RoturIcnStatus::NullPointer;
+        };
+
+        let size = match view.renderer().try_scaled_buf_size() {
+            Ok(size) => size,
+            Err(err) => return RoturIcnStatus::of_render_error(&err),
+        };
+
+        // SAFETY: the pointers aren't null, the caller guarantees they're writable
+        unsafe {
+            width.write(size.0);
+            height.write(size.1);
+        }
+
+        RoturIcnStatus::Ok
+    })
+}
+
+/// Render the ICN into the caller's buffer of straight RGBA pixels, row by row from the top
+///
+/// The buffer has to be exactly as large as [`rotur_icn_render_size`] tells.
+///
+/// # Safety
+///
+/// `icon` must be null or a live ICN, `view` null or readable
+/// & `buf` null or pointing to `len` writable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rotur_icn_render(
+    icon: *const RoturIcn,
+    view: *const RoturIcnView,
+    buf: *mut u8,
+    len: usize,
+) -> RoturIcnStatus {
+    guard(|| {
+        if buf.is_null() {
+            return RoturIcnStatus::NullPointer;
+        }
+
+        // SAFETY: the caller guarantees they're null or valid
+        let (Some(icon), Some(view)) = (unsafe { icon.as_ref() }, unsafe { view.as_ref() }) else {
+            return RoturIcnStatus::NullPointer;
+        };
+
+        let mut renderer = view.renderer();
+
+        // checked before loading, which is the slow part
+        let size = match renderer.try_scaled_buf_size() {
+            Ok(size) => size,
+            Err(err) => return RoturIcnStatus::of_render_error(&err),
+        };
+
+        if size.0 * size.1 * 4 != len {
+            return RoturIcnStatus::WrongBufferSize;
+        }
+
+        // SAFETY: the pointer isn't null, the caller guarantees the length
+        let buf = unsafe { std::slice::from_raw_parts_mut(buf, len) };
+
+        renderer.load(&icon.icon);
+
+        match renderer.try_render(buf) {
+            Ok(()) => RoturIcnStatus::Ok,
+            Err(err) => RoturIcnStatus::of_render_error(&err),
+        }
+    })
+}
@@ -0,0 +1,207 @@
+use std::{
+    ffi::{CStr, c_char},
+    ptr,
+};
+
+use rotur_icn_ffi::{
+    RoturIcn, RoturIcnStatus, RoturIcnView, rotur_icn_error_count, rotur_icn_error_message,
+    rotur_icn_fit, rotur_icn_free, rotur_icn_process, rotur_icn_render, rotur_icn_render_size,
+};
+
+const VIEW: RoturIcnView = RoturIcnView {
+    width: 4.,
+    height: 2.,
+    scale: 2.,
+    camera_x: 0.,
+    camera_y: 0.,
+    background: 0x1020_30ff,
+    pixel_budget: 0,
+};
+
+/// Process the source, as a C caller would, through a pointer & a length
+fn process(src: &[u8]) -> (RoturIcnStatus, *mut RoturIcn) {
+    let mut icon = ptr::null_mut();
+
+    // SAFETY: the source is readable for its length & the ICN pointer is writable
+    let status = unsafe { rotur_icn_process(src.as_ptr().cast(), src.len(), &raw mut icon) };
+
+    (status, icon)
+}
+
+fn render_size(view: &RoturIcnView) -> Result<(usize, usize), RoturIcnStatus> {
+    let (mut width, mut height) = (0, 0);
+
+    // SAFETY: the view is readable, the sizes are writable
+    match unsafe { rotur_icn_render_size(view, &raw mut width, &raw mut height) } {
+        RoturIcnStatus::Ok => Ok((width, height)),
+        status => Err(status),
+    }
+}
+
+#[test]
+fn null_pointers_are_reported() {
+    let mut icon = ptr::null_mut();
+    let mut size = 0;
+
+    // SAFETY: every pointer is either null or valid
+    unsafe {
+        assert_eq!(
+            rotur_icn_process(ptr::null(), 0, &raw mut icon),
+            RoturIcnStatus::NullPointer
+        );
+        assert_eq!(
+            rotur_icn_process(c"dot 0 0".as_ptr(), 7, ptr::null_mut()),
+            RoturIcnStatus::NullPointer
+        );
+        assert!(icon.is_null(), "the ICN is left alone on failure");
+
+        assert_eq!(rotur_icn_error_count(ptr::null()), 0);
+        assert!(rotur_icn_error_message(ptr::null(), 0).is_null());
+        assert_eq!(
+            rotur_icn_fit(ptr::null(), ptr::null_mut()),
+            RoturIcnStatus::NullPointer
+        );
+        assert_eq!(
+            rotur_icn_render_size(&VIEW, &raw mut size, ptr::null_mut()),
+            RoturIcnStatus::NullPointer
+        );
+        assert_eq!(
+            rotur_icn_render_size(ptr::null(), &raw mut size, &raw mut size),
+            RoturIcnStatus::NullPointer
+        );
+
+        rotur_icn_free(ptr::null_mut());
+    }
+
+    let (status, icon) = process(b"dot 0 0");
+    assert_eq!(status, RoturIcnStatus::Ok);
+
+    // SAFETY: the ICN is live until it's freed, the rest is null
+    unsafe {
+        assert_eq!(
+            rotur_icn_render(icon, &VIEW, ptr::null_mut(), 0),
+            RoturIcnStatus::NullPointer
+        );
+        assert_eq!(
+            rotur_icn_render(icon, ptr::null(), [0].as_mut_ptr(), 1),
+            RoturIcnStatus::NullPointer
+        );
+        rotur_icn_free(icon);
+    }
+}
+
+#[test]
+fn invalid_utf8_is_reported() {
+    let (status, icon) = process(b"dot \xff 0");
+
+    assert_eq!(status, RoturIcnStatus::InvalidUtf8);
+    assert!(icon.is_null());
+}
+
+#[test]
+fn errors_are_counted_with_their_messages() {
+    let (status, icon) = process(b"dot 0 0 x 1");
+    assert_eq!(status, RoturIcnStatus::Ok);
+
+    // SAFETY: the ICN is live until it's freed, the messages as long as it
+    unsafe {
+        assert_eq!(rotur_icn_error_count(icon), 1);
+
+        let message: *const c_char = rotur_icn_error_message(icon, 0);
+        assert!(!message.is_null());
+        assert!(
+            CStr::from_ptr(message)
+                .to_string_lossy()
+                .contains("unknown command")
+        );
+
+        assert!(rotur_icn_error_message(icon, 1).is_null());
+        rotur_icn_free(icon);
+    }
+}
+
+#[test]
+fn empty_icn_renders_the_background() {
+    let (status, icon) = process(b"");
+    assert_eq!(status, RoturIcnStatus::Ok);
+
+    let size = render_size(&VIEW).expect("the view is valid");
+    assert_eq!(size, (8, 4));
+
+    let mut buf = vec![0; size.0 * size.1 * 4];
+
+    // SAFETY: the ICN is live until it's freed, the buffer is writable for its length
+    unsafe {
+        assert_eq!(rotur_icn_error_count(icon), 0);
+        assert_eq!(
+            rotur_icn_render(icon, &VIEW, buf.as_mut_ptr(), buf.len()),
+            RoturIcnStatus::Ok
+        );
+    }
+
+    assert!(
+        buf.chunks_exact(4)
+            .all(|pixel| pixel == [0x10, 0x20, 0x30, 0xff])
+    );
+
+    let mut view = VIEW;
+
+    // SAFETY: the ICN is live until it's freed, the view is writable
+    unsafe {
+        assert_eq!(rotur_icn_fit(icon, &raw mut view), RoturIcnStatus::Ok);
+        rotur_icn_free(icon);
+    }
+
+    assert!(view.width.is_finite() && view.height.is_finite());
+}
+
+#[test]
+fn wrong_buffer_length_is_reported() {
+    let (_, icon) = process(b"dot 0 0");
+
+    let (width, height) = render_size(&VIEW).expect("the view is valid");
+    let mut buf = vec![0; width * height * 4 + 1];
+
+    // SAFETY: the ICN is live until it's freed, the buffer is writable for the given lengths
+    unsafe {
+        assert_eq!(
+            rotur_icn_render(icon, &VIEW, buf.as_mut_ptr(), buf.len()),
+            RoturIcnStatus::WrongBufferSize
+        );
+        assert_eq!(
+            rotur_icn_render(icon, &VIEW, buf.as_mut_ptr(), buf.len() - 2),
+            RoturIcnStatus::WrongBufferSize
+        );
+        rotur_icn_free(icon);
+    }
+}
+
+#[test]
+fn unrenderable_views_are_reported() {
+    let too_large = RoturIcnView {
+        pixel_budget: 31,
+        ..VIEW
+    };
+    let non_finite = RoturIcnView {
+        scale: f32::NAN,
+        ..VIEW
+    };
+
+    assert_eq!(render_size(&too_large), Err(RoturIcnStatus::CanvasTooLarge));
+    assert_eq!(
+        render_size(&non_finite),
+        Err(RoturIcnStatus::NonFiniteParameter)
+    );
+
+    let (_, icon) = process(b"dot 0 0");
+    let mut buf = vec![0; 8 * 4 * 4];
+
+    // SAFETY: the ICN is live until it's freed, the buffer is writable for its length
+    unsafe {
+        assert_eq!(
+            rotur_icn_render(icon, &raw const too_large, buf.as_mut_ptr(), buf.len()),
+            RoturIcnStatus::CanvasTooLarge
+        );
+        rotur_icn_free(icon);
+    }
+}
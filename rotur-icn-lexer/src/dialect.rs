@@ -0,0 +1,8 @@
+/// Extensions of the ICN language, which originOS doesn't understand
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Dialect {
+    /// `//` line comments, lexed along with whitespace as trivia
+    ///
+    /// Like colours, a comment must be separated from a preceding number by whitespace.
+    pub comments: bool,
+}
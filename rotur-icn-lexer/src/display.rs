@@ -1,6 +1,6 @@
 use std::fmt;
 
-use super::{Identifier, Literal, LiteralKind, Loc, Pos, Token};
+use super::{Identifier, Literal, LiteralKind, Loc, Pos, Token, Trivia};
 
 pub struct LocDisplay<'l>(pub &'l Loc);
 
@@ -30,6 +30,16 @@ impl fmt::Display for Token<'_> {
         match self {
             Self::Identifier(kw) => write!(f, "kw {kw}"),
             Self::Literal(lit) => write!(f, "lit {lit}"),
+            Self::Trivia(trivia) => write!(f, "trivia {trivia}"),
+        }
+    }
+}
+
+impl fmt::Display for Trivia<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Whitespace(ws) => write!(f, "{ws:?}"),
+            Self::Comment(comment) => write!(f, "{comment:?}"),
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use super::{Pos, display::PosDisplay};
+use super::{Pos, Suggestion, display::PosDisplay};
 
 #[derive(Debug, Clone)]
 pub struct Error {
@@ -18,7 +18,7 @@ pub enum ErrorKind {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", PosDisplay(&self.pos), self.kind,)
+        write!(f, "{} {}", PosDisplay(&self.pos), self.kind)
     }
 }
 
@@ -33,6 +33,18 @@ impl fmt::Display for ErrorKind {
     }
 }
 
+impl Error {
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        match self.kind {
+            // all of these are lexed as a default literal or ignored
+            ErrorKind::StrandedNumber | ErrorKind::StrandedColour | ErrorKind::InvalidToken => {
+                Some(Suggestion::remove(self.pos))
+            }
+            ErrorKind::InvalidColour => None,
+        }
+    }
+}
+
 impl ErrorKind {
     pub fn code(&self) -> &'static str {
         match self {
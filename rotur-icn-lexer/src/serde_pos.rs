@@ -0,0 +1,59 @@
+//! (De)serialising a [`Pos`] as its `start` & `end`, for `#[serde(with = "...")]`
+//!
+//! Each holds its zero-based `line`, `col` (in unicode chars) & `byte_idx`.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::token::{Loc, Pos};
+
+#[derive(Serialize, Deserialize)]
+struct LocRepr {
+    line: u32,
+    col: u32,
+    byte_idx: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PosRepr {
+    start: LocRepr,
+    end: LocRepr,
+}
+
+impl From<Loc> for LocRepr {
+    fn from(loc: Loc) -> Self {
+        Self {
+            line: loc.line,
+            col: loc.col,
+            byte_idx: loc.byte_idx,
+        }
+    }
+}
+
+impl From<LocRepr> for Loc {
+    fn from(loc: LocRepr) -> Self {
+        Self {
+            line: loc.line,
+            col: loc.col,
+            byte_idx: loc.byte_idx,
+        }
+    }
+}
+
+/// # Errors
+///
+/// If the serialiser fails.
+pub fn serialize<S: Serializer>(pos: &Pos, serializer: S) -> Result<S::Ok, S::Error> {
+    PosRepr {
+        start: pos.0.into(),
+        end: pos.1.into(),
+    }
+    .serialize(serializer)
+}
+
+/// # Errors
+///
+/// If the input isn't a position.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pos, D::Error> {
+    let pos = PosRepr::deserialize(deserializer)?;
+    Ok((pos.start.into(), pos.end.into()))
+}
@@ -0,0 +1,42 @@
+use std::{borrow::Cow, fmt, ops::Range};
+
+use super::Pos;
+
+/// A fix for an error, which can be applied as is: the source at `pos` is replaced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub pos: Pos,
+    /// Empty, when the source is removed
+    pub replacement: Cow<'static, str>,
+}
+
+impl Suggestion {
+    pub fn remove(pos: Pos) -> Self {
+        Self {
+            pos,
+            replacement: Cow::Borrowed(""),
+        }
+    }
+
+    pub fn replace(pos: Pos, replacement: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            pos,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// The byte range of the replaced source
+    pub fn range(&self) -> Range<usize> {
+        self.pos.0.byte_idx..self.pos.1.byte_idx
+    }
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.replacement.is_empty() {
+            write!(f, "remove this")
+        } else {
+            write!(f, "replace with `{}`", self.replacement)
+        }
+    }
+}
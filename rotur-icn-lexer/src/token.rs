@@ -5,17 +5,31 @@ pub type Pos = (Loc, Loc);
 pub type PToken<'s> = (Loc, Token<'s>, Loc);
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Token<'s> {
     Identifier(Identifier<'s>),
     Literal(Literal),
+    /// Only lexed in a dialect with comments
+    Trivia(Trivia<'s>),
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Identifier<'s> {
     pub value: &'s str,
 }
 
+/// Source, which doesn't affect the ICN, kept for reprinting it
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Trivia<'s> {
+    Whitespace(&'s str),
+    /// A line comment, including the leading `//`
+    Comment(&'s str),
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Literal {
     Colour(Colour),
     Number(Number),
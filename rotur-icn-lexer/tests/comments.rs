@@ -0,0 +1,90 @@
+use rotur_icn_lexer::{
+    Dialect, Error, ErrorKind,
+    token::{Literal, Token, Trivia},
+};
+
+const COMMENTS: Dialect = Dialect { comments: true };
+
+/// Source of each token & the errors found
+fn lex_with(src: &str, dialect: Dialect) -> (Vec<String>, Vec<Error>) {
+    let mut errors = Vec::new();
+
+    let tokens = rotur_icn_lexer::lex_with(&mut errors, src, dialect)
+        .map(|(start, token, end)| {
+            let text = &src[start.byte_idx..end.byte_idx];
+
+            match token {
+                Token::Identifier(ident) => assert_eq!(ident.value, text),
+                Token::Literal(Literal::Number(_) | Literal::Colour(_)) => {}
+                Token::Trivia(Trivia::Whitespace(ws)) => assert_eq!(ws, text),
+                Token::Trivia(Trivia::Comment(comment)) => assert_eq!(comment, text.trim_end()),
+            }
+
+            text.to_owned()
+        })
+        .collect();
+
+    (tokens, errors)
+}
+
+#[test]
+fn comments_and_whitespace_are_lexed_as_trivia() {
+    let src = "// a dot\ndot 0 0 // at the centre 😀\n\n  // the end";
+    let (tokens, errors) = lex_with(src, COMMENTS);
+
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(
+        tokens,
+        [
+            "// a dot",
+            "\n",
+            "dot",
+            " ",
+            "0",
+            " ",
+            "0",
+            " ",
+            "// at the centre 😀",
+            "\n\n  ",
+            "// the end",
+        ]
+    );
+
+    // nothing's left out in between
+    assert_eq!(tokens.concat(), src);
+}
+
+#[test]
+fn comments_keep_their_trailing_whitespace_out() {
+    let src = "dot 0 0 // spaces after   \nline";
+    let mut errors = Vec::new();
+
+    let comments = rotur_icn_lexer::lex_with(&mut errors, src, COMMENTS)
+        .filter_map(|(_, token, _)| match token {
+            Token::Trivia(Trivia::Comment(comment)) => Some(comment),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(comments, ["// spaces after"]);
+}
+
+#[test]
+fn comments_are_invalid_tokens_without_the_dialect() {
+    let src = "dot 0 0 // a dot";
+    let (tokens, errors) = lex_with(src, Dialect::default());
+
+    // whitespace is skipped & the comment's words are lexed as commands
+    assert_eq!(tokens, ["dot", "0", "0", "a", "dot"]);
+
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [Error {
+                kind: ErrorKind::InvalidToken,
+                pos: (start, end),
+            }] if (start.byte_idx, end.byte_idx) == (8, 10)
+        ),
+        "{errors:?}"
+    );
+}
@@ -0,0 +1,107 @@
+use rotur_icn_lowerer::hir;
+use rotur_icn_resolver::{Compat, Resolver, lir};
+use rotur_icn_units::Number;
+
+mod lint;
+mod warning;
+
+pub use lint::{Config, Level, Lint, UnknownLintError};
+pub use warning::{Collapsed, Warning, WarningKind};
+
+/// Half of the size of the canvas originOS draws ICNs on, which is centred on the origin
+const CANVAS_HALF_SIZE: Number = 10.;
+
+/// Find commands which are valid, but likely not what was meant
+///
+/// Lints allowed by the config aren't reported,
+/// the others carry their configured level.
+/// Shapes are placed as resolving with the compat does.
+pub fn lint(icon: &hir::IconHir, compat: Compat, config: &Config) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    let mut warn = |op: &hir::Operation, op_i: usize, kind: WarningKind| {
+        let level = config.level(kind.lint());
+
+        if level != Level::Allow {
+            warnings.push(Warning {
+                cmd_pos: op.cmd_pos,
+                cmd_index: op_i,
+                level,
+                kind,
+            });
+        }
+    };
+
+    let mut resolver = Resolver::new(compat);
+    let mut errors = Vec::new();
+
+    // the latest colour, which nothing was drawn with yet
+    let mut unused_colour: Option<usize> = None;
+
+    for (op_i, op) in icon.operations.iter().enumerate() {
+        match &op.kind {
+            hir::OperationKind::SetWidth(set_width) if set_width.value == 0. => {
+                warn(op, op_i, WarningKind::ZeroWidth);
+            }
+            hir::OperationKind::SetColour(_) => {
+                if let Some(prev_i) = unused_colour.replace(op_i) {
+                    warn(
+                        &icon.operations[prev_i],
+                        prev_i,
+                        WarningKind::UnusedColour {
+                            overridden_pos: Some(op.cmd_pos),
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        let Some(el) = resolver.step(&mut errors, op_i, op) else {
+            continue;
+        };
+
+        unused_colour = None;
+
+        // taken from what the triangle resolves to, so that it's what's drawn
+        if let hir::OperationKind::DrawTriangle(_) = op.kind {
+            let collapsed_into = match el.kind {
+                lir::ElementKind::Disk(_) => Some(Collapsed::Dot),
+                lir::ElementKind::Line(_) => Some(Collapsed::Line),
+                _ => None,
+            };
+
+            if let Some(collapsed_into) = collapsed_into {
+                warn(op, op_i, WarningKind::DegenerateTriangle { collapsed_into });
+            }
+        }
+
+        if is_off_canvas(&el) {
+            warn(op, op_i, WarningKind::OffCanvas);
+        }
+    }
+
+    if let Some(prev_i) = unused_colour {
+        warn(
+            &icon.operations[prev_i],
+            prev_i,
+            WarningKind::UnusedColour {
+                overridden_pos: None,
+            },
+        );
+    }
+
+    // unused colours are only found later on
+    warnings.sort_by_key(|warning| warning.cmd_index);
+
+    warnings
+}
+
+fn is_off_canvas(el: &lir::Element) -> bool {
+    let (bl, tr) = el.bounds();
+
+    tr.x <= -CANVAS_HALF_SIZE
+        || tr.y <= -CANVAS_HALF_SIZE
+        || bl.x >= CANVAS_HALF_SIZE
+        || bl.y >= CANVAS_HALF_SIZE
+}
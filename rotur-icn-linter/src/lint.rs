@@ -0,0 +1,121 @@
+use std::{fmt, str::FromStr};
+
+/// A kind of suspicious command, which can be configured on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lint {
+    ZeroWidth,
+    OffCanvas,
+    UnusedColour,
+    DegenerateTriangle,
+}
+
+impl Lint {
+    pub const ALL: [Self; 4] = [
+        Self::ZeroWidth,
+        Self::OffCanvas,
+        Self::UnusedColour,
+        Self::DegenerateTriangle,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::ZeroWidth => "WL00",
+            Self::OffCanvas => "WL01",
+            Self::UnusedColour => "WL02",
+            Self::DegenerateTriangle => "WL03",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ZeroWidth => "zero-width",
+            Self::OffCanvas => "off-canvas",
+            Self::UnusedColour => "unused-colour",
+            Self::DegenerateTriangle => "degenerate-triangle",
+        }
+    }
+
+    pub fn default_level(self) -> Level {
+        match self {
+            Self::ZeroWidth | Self::OffCanvas | Self::UnusedColour | Self::DegenerateTriangle => {
+                Level::Warn
+            }
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = UnknownLintError;
+
+    /// Find the lint by its name or code
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|lint| lint.name() == s || lint.code().eq_ignore_ascii_case(s))
+            .ok_or(UnknownLintError)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnknownLintError;
+
+impl fmt::Display for UnknownLintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown lint")
+    }
+}
+
+impl std::error::Error for UnknownLintError {}
+
+/// How a found lint is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Not reported at all
+    Allow,
+    /// Reported as a warning, which doesn't fail the ICN
+    Warn,
+    /// Reported as an error
+    Deny,
+}
+
+/// The level of every lint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    levels: [Level; Lint::ALL.len()],
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            levels: Lint::ALL.map(Lint::default_level),
+        }
+    }
+}
+
+impl Config {
+    pub fn level(&self, lint: Lint) -> Level {
+        self.levels[lint as usize]
+    }
+
+    pub fn set(&mut self, lint: Lint, level: Level) {
+        self.levels[lint as usize] = level;
+    }
+
+    pub fn allow(&mut self, lint: Lint) {
+        self.set(lint, Level::Allow);
+    }
+
+    pub fn warn(&mut self, lint: Lint) {
+        self.set(lint, Level::Warn);
+    }
+
+    pub fn deny(&mut self, lint: Lint) {
+        self.set(lint, Level::Deny);
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use rotur_icn_lexer::{display::PosDisplay, token};
+
+use crate::{Level, Lint};
+
+#[derive(Debug, Clone)]
+pub struct Warning {
+    pub cmd_pos: token::Pos,
+    pub cmd_index: usize,
+    pub level: Level,
+    pub kind: WarningKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum WarningKind {
+    ZeroWidth,
+    OffCanvas,
+    UnusedColour {
+        /// The colour it's replaced by, if any
+        overridden_pos: Option<token::Pos>,
+    },
+    DegenerateTriangle {
+        collapsed_into: Collapsed,
+    },
+}
+
+/// What a triangle with coinciding corners is drawn as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collapsed {
+    Line,
+    Dot,
+}
+
+impl Warning {
+    pub fn is_denied(&self) -> bool {
+        self.level == Level::Deny
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cmd #{} {} : {}",
+            self.cmd_index + 1,
+            PosDisplay(&self.cmd_pos),
+            self.kind,
+        )
+    }
+}
+
+impl fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroWidth => write!(f, "the width is set to zero"),
+            Self::OffCanvas => write!(f, "the shape is drawn fully outside of the canvas"),
+            Self::UnusedColour {
+                overridden_pos: Some(_),
+            } => write!(f, "the colour is replaced before anything is drawn with it"),
+            Self::UnusedColour {
+                overridden_pos: None,
+            } => write!(f, "nothing is drawn with the colour"),
+            Self::DegenerateTriangle {
+                collapsed_into: Collapsed::Line,
+            } => write!(
+                f,
+                "the triangle has coinciding corners, so it's drawn as a line"
+            ),
+            Self::DegenerateTriangle {
+                collapsed_into: Collapsed::Dot,
+            } => write!(
+                f,
+                "all corners of the triangle coincide, so it's drawn as a dot"
+            ),
+        }
+    }
+}
+
+impl WarningKind {
+    pub fn lint(&self) -> Lint {
+        match self {
+            Self::ZeroWidth => Lint::ZeroWidth,
+            Self::OffCanvas => Lint::OffCanvas,
+            Self::UnusedColour { .. } => Lint::UnusedColour,
+            Self::DegenerateTriangle { .. } => Lint::DegenerateTriangle,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.lint().code()
+    }
+
+    pub fn help(&self) -> Option<&'static str> {
+        match self {
+            Self::ZeroWidth => Some("lines & outlines drawn with zero width are invisible"),
+            Self::OffCanvas => Some("the canvas spans from -10 to 10 on both axes"),
+            Self::UnusedColour { .. } => Some("the command can be removed"),
+            Self::DegenerateTriangle {
+                collapsed_into: Collapsed::Line,
+            } => Some("use `line` to draw a line"),
+            Self::DegenerateTriangle {
+                collapsed_into: Collapsed::Dot,
+            } => Some("use `dot` to draw a dot"),
+        }
+    }
+}
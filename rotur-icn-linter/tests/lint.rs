@@ -0,0 +1,137 @@
+use rotur_icn_linter::{Collapsed, Config, Level, Lint, Warning, WarningKind};
+use rotur_icn_resolver::Compat;
+
+fn lint_with(src: &str, config: &Config) -> Vec<Warning> {
+    let mut lexing_errors = Vec::new();
+    let (icon, parsing_errors) =
+        rotur_icn_parser::parse(rotur_icn_lexer::lex(&mut lexing_errors, src));
+    let (icon, lowering_errors) = rotur_icn_lowerer::lower(&icon);
+
+    assert!(
+        lexing_errors.is_empty() && parsing_errors.is_empty() && lowering_errors.is_empty(),
+        "{src:?} should be valid"
+    );
+
+    rotur_icn_linter::lint(&icon, Compat::default(), config)
+}
+
+/// Commands & the lints found in them
+fn lints(src: &str) -> Vec<(usize, Lint)> {
+    lint_with(src, &Config::default())
+        .iter()
+        .map(|warning| (warning.cmd_index, warning.kind.lint()))
+        .collect()
+}
+
+#[test]
+fn zero_width_is_found() {
+    assert_eq!(lints("w 0 line 0 0 1 1"), [(0, Lint::ZeroWidth)]);
+    assert_eq!(lints("w 0.5 line 0 0 1 1"), []);
+}
+
+#[test]
+fn shapes_fully_off_canvas_are_found() {
+    assert_eq!(
+        lints("dot 20 0 dot 0 0 w 1 line 11 11 15 15 dot 0 -30"),
+        [
+            (0, Lint::OffCanvas),
+            (3, Lint::OffCanvas),
+            (4, Lint::OffCanvas)
+        ]
+    );
+
+    // its edge still reaches into the canvas
+    assert_eq!(lints("dot 11 0"), []);
+}
+
+#[test]
+fn overridden_colours_are_unused() {
+    let warnings = lint_with("c #f00 c #0f0 dot 0 0", &Config::default());
+
+    assert!(matches!(
+        warnings.as_slice(),
+        [Warning {
+            cmd_index: 0,
+            kind: WarningKind::UnusedColour {
+                overridden_pos: Some(_)
+            },
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn trailing_colours_are_unused() {
+    let warnings = lint_with("c #f00 dot 0 0 c #0f0", &Config::default());
+
+    assert!(matches!(
+        warnings.as_slice(),
+        [Warning {
+            cmd_index: 2,
+            kind: WarningKind::UnusedColour {
+                overridden_pos: None
+            },
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn used_colours_are_not_found() {
+    assert_eq!(lints("c #f00 dot 0 0 c #0f0 dot 1 1"), []);
+}
+
+#[test]
+fn degenerate_triangles_are_found_with_what_they_collapse_into() {
+    let collapsed = |src: &str| {
+        lint_with(src, &Config::default())
+            .into_iter()
+            .map(|warning| match warning.kind {
+                WarningKind::DegenerateTriangle { collapsed_into } => collapsed_into,
+                kind => panic!("{kind} shouldn't be found in {src:?}"),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(collapsed("tri 0 0 0 0 3 1"), [Collapsed::Line]);
+    assert_eq!(collapsed("tri 1 1 1 1 1 1"), [Collapsed::Dot]);
+    assert_eq!(collapsed("tri 0 0 1 1 2 0"), []);
+    // only coinciding corners are collapsed, not ones along a line
+    assert_eq!(collapsed("tri 0 0 1 1 2 2"), []);
+}
+
+#[test]
+fn lints_are_reported_at_their_configured_levels() {
+    let src = "w 0 c #f00 dot 20 0";
+
+    let mut config = Config::default();
+    config.allow(Lint::ZeroWidth);
+    config.deny(Lint::OffCanvas);
+
+    let levels = lint_with(src, &config)
+        .iter()
+        .map(|warning| (warning.kind.lint(), warning.level))
+        .collect::<Vec<_>>();
+
+    assert_eq!(levels, [(Lint::OffCanvas, Level::Deny)]);
+
+    config.warn(Lint::ZeroWidth);
+    config.allow(Lint::OffCanvas);
+
+    let levels = lint_with(src, &config)
+        .iter()
+        .map(|warning| (warning.kind.lint(), warning.level))
+        .collect::<Vec<_>>();
+
+    assert_eq!(levels, [(Lint::ZeroWidth, Level::Warn)]);
+}
+
+#[test]
+fn lints_are_found_by_name_or_code() {
+    for lint in Lint::ALL {
+        assert_eq!(lint.name().parse::<Lint>().ok(), Some(lint));
+        assert_eq!(lint.code().to_lowercase().parse::<Lint>().ok(), Some(lint));
+    }
+
+    assert!("zero_width".parse::<Lint>().is_err());
+}
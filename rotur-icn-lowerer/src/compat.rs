@@ -0,0 +1,28 @@
+use rotur_icn_units::Number;
+
+/// Semantics, which were derived independently of originOS & may differ from how it draws
+///
+/// The defaults are what the regression snapshot pins down, not a preset known to match originOS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Compat {
+    /// Largest angle in degrees of `cutcircle`'s arms from its direction, to which larger ones are clamped
+    pub max_arm_angle: Number,
+    /// Width of lines & outlines before the first `w`
+    pub default_width: Number,
+    /// Degrees per unit of `cutcircle`'s direction
+    pub arc_direction_scale: Number,
+    /// Whether a continued line starts at the last point moved by the centre once more,
+    /// otherwise it starts exactly at the last point
+    pub offset_continued_lines: bool,
+}
+
+impl Default for Compat {
+    fn default() -> Self {
+        Self {
+            max_arm_angle: 180.,
+            default_width: 5.,
+            arc_direction_scale: 10.,
+            offset_continued_lines: true,
+        }
+    }
+}
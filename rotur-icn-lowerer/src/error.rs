@@ -1,6 +1,6 @@
-use std::fmt;
+use std::{borrow::Cow, fmt};
 
-use rotur_icn_lexer::{display::PosDisplay, token};
+use rotur_icn_lexer::{Suggestion, display::PosDisplay, token};
 use rotur_icn_units::Number;
 
 #[derive(Debug, Clone)]
@@ -35,10 +35,26 @@ pub enum ErrorKind {
     ArgOutOfRange {
         arg_pos: token::Pos,
         arg_index: usize,
+        value: Number,
         range_start: Option<(Number, bool)>,
         range_end: Option<(Number, bool)>,
     },
-    InvalidCommand,
+    InvalidCommand {
+        /// What the command is called in ICN, if it's a common name from elsewhere
+        alias_of: Option<Alias>,
+        /// Known commands with a similar name, closest first
+        similar: Vec<&'static str>,
+        /// The similar command, if no other one is as close
+        closest: Option<&'static str>,
+    },
+}
+
+/// A known command, which an invalid one is a common name of in other drawing languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alias {
+    pub command: &'static str,
+    /// Whether it takes the same arguments, so that renaming it is enough
+    pub same_args: bool,
 }
 
 impl fmt::Display for Error {
@@ -95,6 +111,7 @@ impl fmt::Display for ErrorKind {
             Self::ArgOutOfRange {
                 arg_pos: _,
                 arg_index,
+                value: _,
                 range_start,
                 range_end,
             } => {
@@ -132,13 +149,46 @@ impl fmt::Display for ErrorKind {
 
                 Ok(())
             }
-            Self::InvalidCommand => {
+            Self::InvalidCommand { .. } => {
                 write!(f, "unknown command")
             }
         }
     }
 }
 
+impl Error {
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        match &self.kind {
+            ErrorKind::TooManyArguments { overflow_pos, .. } => {
+                Some(Suggestion::remove(*overflow_pos))
+            }
+            // the value is clamped only to an inclusive bound, an exclusive one can't be reached
+            ErrorKind::ArgOutOfRange {
+                arg_pos,
+                value,
+                range_start,
+                range_end,
+                ..
+            } => match (range_start, range_end) {
+                (Some((start, true)), _) if value < start => {
+                    Some(Suggestion::replace(*arg_pos, start.to_string()))
+                }
+                (_, Some((end, true))) if value > end => {
+                    Some(Suggestion::replace(*arg_pos, end.to_string()))
+                }
+                _ => None,
+            },
+            ErrorKind::InvalidCommand { .. } => self
+                .kind
+                .intended_command()
+                .map(|intended| Suggestion::replace(self.cmd_pos, intended)),
+            ErrorKind::TooFewArguments { .. }
+            | ErrorKind::UnexpectedLiteralKind { .. }
+            | ErrorKind::InvalidNumericColour { .. } => None,
+        }
+    }
+}
+
 impl ErrorKind {
     pub fn code(&self) -> &'static str {
         match self {
@@ -147,20 +197,67 @@ impl ErrorKind {
             ErrorKind::UnexpectedLiteralKind { .. } => "EW02",
             ErrorKind::InvalidNumericColour { .. } => "EW03",
             ErrorKind::ArgOutOfRange { .. } => "EW04",
-            ErrorKind::InvalidCommand => "EW05",
+            ErrorKind::InvalidCommand { .. } => "EW05",
         }
     }
 
-    pub fn help(&self) -> Option<&'static str> {
+    pub fn help(&self) -> Option<Cow<'static, str>> {
         match self {
-            Self::InvalidNumericColour { .. } => {
-                Some("numerical colours should fit in a u24 (max: 16777215)")
-            }
+            Self::InvalidNumericColour { .. } => Some(Cow::Borrowed(
+                "numerical colours should fit in a u24 (max: 16777215)",
+            )),
             Self::UnexpectedLiteralKind {
                 got: token::LiteralKind::Colour,
                 ..
-            } => Some("colours cannot be used as hex numbers; did you use a correct command?"),
-            // TODO help for invalid commands
+            } => Some(Cow::Borrowed(
+                "colours cannot be used as hex numbers; did you use a correct command?",
+            )),
+            Self::InvalidCommand {
+                alias_of:
+                    Some(Alias {
+                        command,
+                        same_args: true,
+                    }),
+                ..
+            } => Some(Cow::Owned(format!(
+                "in ICN this command is called `{command}`"
+            ))),
+            Self::InvalidCommand {
+                alias_of:
+                    Some(Alias {
+                        command,
+                        same_args: false,
+                    }),
+                ..
+            } => Some(Cow::Owned(format!(
+                "the closest command in ICN is `{command}`, which takes different arguments"
+            ))),
+            Self::InvalidCommand {
+                alias_of: None,
+                similar,
+                ..
+            } => similar
+                .first()
+                .map(|closest| Cow::Owned(format!("did you mean `{closest}`?"))),
+            _ => None,
+        }
+    }
+
+    /// The known command, which was most likely meant instead of an invalid one,
+    /// if it can replace the invalid one as it is
+    ///
+    /// Neither an alias taking different arguments nor one of several equally close commands is.
+    pub fn intended_command(&self) -> Option<&'static str> {
+        match self {
+            Self::InvalidCommand {
+                alias_of: Some(alias_of),
+                ..
+            } => alias_of.same_args.then_some(alias_of.command),
+            Self::InvalidCommand {
+                alias_of: None,
+                closest,
+                ..
+            } => *closest,
             _ => None,
         }
     }
@@ -2,17 +2,21 @@ use rotur_icn_lexer::token;
 use rotur_icn_units::{Colour, Number, Vector};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IconHir {
     pub operations: Vec<Operation>,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Operation {
+    #[cfg_attr(feature = "serde", serde(with = "rotur_icn_lexer::serde_pos"))]
     pub cmd_pos: token::Pos,
     pub kind: OperationKind,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OperationKind {
     SetWidth(SetWidth),
     SetColour(SetColour),
@@ -63,7 +67,25 @@ impl OperationKind {
     }
 }
 
+/// Names of every command, in the order of [`OperationKind`]
+pub const COMMAND_NAMES: [&str; 13] = [
+    SetWidth::NAME,
+    SetColour::NAME,
+    DrawLine::NAME,
+    ContinueLine::NAME,
+    DrawDisk::NAME,
+    DrawRectangle::NAME_HOLLOW,
+    DrawRectangle::NAME_FILLED,
+    DrawTriangle::NAME,
+    MoveCentre::NAME,
+    ResetCentre::NAME,
+    DrawArc::NAME,
+    DrawEllipse::NAME,
+    DrawCurve::NAME,
+];
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SetWidth {
     pub value: Number,
 }
@@ -73,6 +95,7 @@ impl SetWidth {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SetColour {
     pub value: Colour,
 }
@@ -82,6 +105,7 @@ impl SetColour {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawLine {
     pub start: Vector,
     pub end: Vector,
@@ -92,6 +116,7 @@ impl DrawLine {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ContinueLine {
     pub next: Vector,
 }
@@ -101,6 +126,7 @@ impl ContinueLine {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawDisk {
     pub centre: Vector,
 }
@@ -110,6 +136,7 @@ impl DrawDisk {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawRectangle {
     pub centre: Vector,
     pub sizes: Vector,
@@ -122,6 +149,7 @@ impl DrawRectangle {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawTriangle {
     pub a: Vector,
     pub b: Vector,
@@ -133,6 +161,7 @@ impl DrawTriangle {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoveCentre {
     pub change: Vector,
 }
@@ -142,6 +171,7 @@ impl MoveCentre {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResetCentre;
 
 impl ResetCentre {
@@ -149,6 +179,7 @@ impl ResetCentre {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawArc {
     pub centre: Vector,
     pub radius: Number,
@@ -161,6 +192,7 @@ impl DrawArc {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawEllipse {
     pub centre: Vector,
     pub major: Number,
@@ -173,6 +205,7 @@ impl DrawEllipse {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawCurve {
     pub start: Vector,
     pub control: Vector,
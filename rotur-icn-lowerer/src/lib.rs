@@ -4,17 +4,25 @@ use rotur_icn_lexer::token;
 use rotur_icn_parser::ast;
 use rotur_icn_units::{Colour, Number, Vector};
 
+mod compat;
 mod display;
 mod error;
 pub mod hir;
+mod spelling;
 
-pub use error::{Error, ErrorKind};
+pub use compat::Compat;
+pub use error::{Alias, Error, ErrorKind};
 use hir::{
     ContinueLine, DrawArc, DrawCurve, DrawDisk, DrawEllipse, DrawLine, DrawRectangle, DrawTriangle,
     IconHir, MoveCentre, Operation, OperationKind, ResetCentre, SetColour, SetWidth,
 };
 
 pub fn lower(icon: &ast::Icon) -> (IconHir, Vec<Error>) {
+    lower_with(icon, Compat::default())
+}
+
+#[expect(clippy::too_many_lines, reason = "a flat match over every command")]
+pub fn lower_with(icon: &ast::Icon, compat: Compat) -> (IconHir, Vec<Error>) {
     let mut errors = Vec::new();
 
     let mut operations = Vec::with_capacity(icon.commands.len());
@@ -146,10 +154,10 @@ pub fn lower(icon: &ast::Icon) -> (IconHir, Vec<Error>) {
                     cmd,
                     cmd_index,
                     Some((0., true)),
-                    Some((180.0, true)),
+                    Some((compat.max_arm_angle, true)),
                     4,
                 ) {
-                    arm_angle = arm_angle.clamp(0.0, 180.0);
+                    arm_angle = arm_angle.clamp(0.0, compat.max_arm_angle);
                 }
 
                 Operation {
@@ -174,7 +182,7 @@ pub fn lower(icon: &ast::Icon) -> (IconHir, Vec<Error>) {
                     width = 0.;
                 }
 
-                if !validate_arg_value(&mut errors, cmd, cmd_index, Some((0., true)), None, 2) {
+                if !validate_arg_value(&mut errors, cmd, cmd_index, Some((0., true)), None, 3) {
                     ratio = 0.;
                 }
 
@@ -205,10 +213,16 @@ pub fn lower(icon: &ast::Icon) -> (IconHir, Vec<Error>) {
                 }
             }
             _ => {
+                let (similar, closest) = spelling::similar_commands(cmd.name);
+
                 errors.push(Error {
                     cmd_pos: cmd.name_pos,
                     cmd_index,
-                    kind: ErrorKind::InvalidCommand,
+                    kind: ErrorKind::InvalidCommand {
+                        alias_of: spelling::alias_of(cmd.name),
+                        similar,
+                        closest,
+                    },
                 });
 
                 continue;
@@ -293,6 +307,7 @@ fn validate_arg_value(
             kind: ErrorKind::ArgOutOfRange {
                 arg_pos: *arg_pos,
                 arg_index: i,
+                value: *value,
                 range_start,
                 range_end,
             },
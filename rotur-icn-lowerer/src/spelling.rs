@@ -0,0 +1,133 @@
+use crate::{
+    Alias,
+    hir::{
+        COMMAND_NAMES, ContinueLine, DrawArc, DrawCurve, DrawDisk, DrawEllipse, DrawRectangle,
+        DrawTriangle, MoveCentre, ResetCentre, SetColour, SetWidth,
+    },
+};
+
+/// Common names from other drawing languages, with the command they are called in ICN,
+/// which take the same arguments there
+const ALIASES: [(&str, &str); 12] = [
+    ("width", SetWidth::NAME),
+    ("linewidth", SetWidth::NAME),
+    ("colour", SetColour::NAME),
+    ("color", SetColour::NAME),
+    ("fill", SetColour::NAME),
+    ("stroke", SetColour::NAME),
+    ("lineto", ContinueLine::NAME),
+    ("point", DrawDisk::NAME),
+    ("triangle", DrawTriangle::NAME),
+    ("translate", MoveCentre::NAME),
+    ("origin", ResetCentre::NAME),
+    ("reset", ResetCentre::NAME),
+];
+
+/// Common names from other drawing languages, with the closest command in ICN,
+/// which takes different arguments, so that renaming them isn't enough
+///
+/// Rectangles elsewhere are placed by a corner, not their centre,
+/// and `moveto` moves the pen to a point, not the centre by an offset.
+const LOOSE_ALIASES: [(&str, &str); 15] = [
+    ("polyline", ContinueLine::NAME),
+    ("disk", DrawDisk::NAME),
+    ("disc", DrawDisk::NAME),
+    ("polygon", DrawTriangle::NAME),
+    ("circle", DrawArc::NAME),
+    ("arc", DrawArc::NAME),
+    ("oval", DrawEllipse::NAME),
+    ("bezier", DrawCurve::NAME),
+    ("quad", DrawCurve::NAME),
+    ("quadratic", DrawCurve::NAME),
+    ("rectangle", DrawRectangle::NAME_FILLED),
+    ("fillrect", DrawRectangle::NAME_FILLED),
+    ("box", DrawRectangle::NAME_HOLLOW),
+    ("strokerect", DrawRectangle::NAME_HOLLOW),
+    ("moveto", MoveCentre::NAME),
+];
+
+/// How many similar commands are reported at most
+const MAX_SIMILAR: usize = 3;
+
+/// The command the name is called in ICN, if it's a common name from another drawing language
+pub fn alias_of(name: &str) -> Option<Alias> {
+    let find = |aliases: &[(&str, &'static str)]| {
+        aliases
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|&(_, command)| command)
+    };
+
+    find(&ALIASES)
+        .map(|command| Alias {
+            command,
+            same_args: true,
+        })
+        .or_else(|| {
+            find(&LOOSE_ALIASES).map(|command| Alias {
+                command,
+                same_args: false,
+            })
+        })
+}
+
+/// Known commands, which the name is likely a misspelling of, closest first,
+/// with the closest one on its own, if no other is as close
+///
+/// Only names a few edits away are considered, so short names don't match everything.
+pub fn similar_commands(name: &str) -> (Vec<&'static str>, Option<&'static str>) {
+    let name = name.to_ascii_lowercase();
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    let mut similar = COMMAND_NAMES
+        .into_iter()
+        .map(|known| (edit_distance(&name, known), known))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect::<Vec<_>>();
+
+    // stable, so equally close ones stay in the order of the commands
+    similar.sort_by_key(|&(distance, _)| distance);
+
+    let closest = match similar.as_slice() {
+        [(_, only)] => Some(*only),
+        [(first, closest), (second, _), ..] if first < second => Some(*closest),
+        _ => None,
+    };
+
+    let similar = similar
+        .into_iter()
+        .take(MAX_SIMILAR)
+        .map(|(_, known)| known)
+        .collect();
+
+    (similar, closest)
+}
+
+/// Optimal string alignment distance, counting chars
+///
+/// Swapping two adjacent chars is a single edit, as it's a common typo.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // distances from the prefixes of `a` up to the last two chars to every prefix of `b`
+    let mut before_last: Vec<usize> = Vec::new();
+    let mut last: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+
+        for j in 1..=b.len() {
+            let substituted = last[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            row[j] = substituted.min(last[j] + 1).min(row[j - 1] + 1);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(before_last[j - 2] + 1);
+            }
+        }
+
+        before_last = std::mem::replace(&mut last, row);
+    }
+
+    last[b.len()]
+}
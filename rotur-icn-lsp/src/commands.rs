@@ -0,0 +1,109 @@
+use rotur_icn_lowerer::hir;
+
+/// A command known to the lowerer, with its arguments
+pub struct CommandInfo {
+    pub name: &'static str,
+    pub args: &'static [&'static str],
+    pub doc: &'static str,
+}
+
+impl CommandInfo {
+    pub fn find(name: &str) -> Option<&'static Self> {
+        COMMANDS.iter().find(|cmd| cmd.name == name)
+    }
+
+    /// The command as it would be written, with its arguments named
+    pub fn signature(&self) -> String {
+        let mut signature = self.name.to_owned();
+
+        for arg in self.args {
+            signature.push(' ');
+            signature.push_str(arg);
+        }
+
+        signature
+    }
+}
+
+/// Every command, in the order of [`hir::COMMAND_NAMES`]
+pub const COMMANDS: [CommandInfo; hir::COMMAND_NAMES.len()] = [
+    CommandInfo {
+        name: hir::SetWidth::NAME,
+        args: &["width"],
+        doc: "set the width of following lines, dots & outlines",
+    },
+    CommandInfo {
+        name: hir::SetColour::NAME,
+        args: &["colour"],
+        doc: "set the colour of following shapes",
+    },
+    CommandInfo {
+        name: hir::DrawLine::NAME,
+        args: &["x1", "y1", "x2", "y2"],
+        doc: "draw a line between two points",
+    },
+    CommandInfo {
+        name: hir::ContinueLine::NAME,
+        args: &["x", "y"],
+        doc: "continue the last line to a point",
+    },
+    CommandInfo {
+        name: hir::DrawDisk::NAME,
+        args: &["x", "y"],
+        doc: "draw a dot, as big as the width",
+    },
+    CommandInfo {
+        name: hir::DrawRectangle::NAME_HOLLOW,
+        args: &["x", "y", "width", "height"],
+        doc: "draw the outline of a rectangle around its centre",
+    },
+    CommandInfo {
+        name: hir::DrawRectangle::NAME_FILLED,
+        args: &["x", "y", "width", "height"],
+        doc: "draw a filled rectangle around its centre",
+    },
+    CommandInfo {
+        name: hir::DrawTriangle::NAME,
+        args: &["x1", "y1", "x2", "y2", "x3", "y3"],
+        doc: "draw a filled triangle",
+    },
+    CommandInfo {
+        name: hir::MoveCentre::NAME,
+        args: &["dx", "dy"],
+        doc: "move the origin of following shapes",
+    },
+    CommandInfo {
+        name: hir::ResetCentre::NAME,
+        args: &[],
+        doc: "move the origin back to 0 0",
+    },
+    CommandInfo {
+        name: hir::DrawArc::NAME,
+        args: &["x", "y", "radius", "direction", "arm_angle"],
+        doc: "draw an arc, spanning `arm_angle` (0 to 180) either way from `direction`",
+    },
+    CommandInfo {
+        name: hir::DrawEllipse::NAME,
+        args: &["x", "y", "width", "ratio", "direction"],
+        doc: "draw the outline of an ellipse, rotated by `direction`",
+    },
+    CommandInfo {
+        name: hir::DrawCurve::NAME,
+        args: &["x1", "y1", "x2", "y2", "cx", "cy"],
+        doc: "draw a curve between two points, bent towards the control point",
+    },
+];
+
+// so that no command the lowerer knows goes undocumented;
+// `==` on strs isn't usable in const, ignoring the case is fine as the names are lowercase
+const _: () = {
+    let mut i = 0;
+
+    while i < COMMANDS.len() {
+        assert!(
+            COMMANDS[i].name.eq_ignore_ascii_case(hir::COMMAND_NAMES[i]),
+            "the commands should be in the order of the lowerer's",
+        );
+        i += 1;
+    }
+};
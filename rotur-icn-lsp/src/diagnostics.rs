@@ -0,0 +1,103 @@
+use codespan_reporting::diagnostic::{self as codespan, LabelStyle};
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString, Uri,
+};
+
+use crate::position::LineIndex;
+
+/// Run the pipeline over the source & convert its errors into LSP diagnostics
+pub fn check(uri: &Uri, src: &str) -> Vec<Diagnostic> {
+    let (_, _, _, errors) = rotur_icn_pipeline::process(src);
+    let index = LineIndex::new(src);
+
+    errors
+        .into_diagnostics()
+        .map(|diag| convert(uri, &index, diag))
+        .collect()
+}
+
+fn convert(uri: &Uri, index: &LineIndex, diag: codespan::Diagnostic<()>) -> Diagnostic {
+    let (primary, secondary): (Vec<_>, Vec<_>) = diag
+        .labels
+        .into_iter()
+        .partition(|label| label.style == LabelStyle::Primary);
+
+    let primary = primary.into_iter().next();
+
+    let mut message = diag.message;
+    if let Some(label) = primary.as_ref().filter(|label| !label.message.is_empty()) {
+        message.push('\n');
+        message.push_str(&label.message);
+    }
+
+    let related_information = secondary
+        .into_iter()
+        .map(|label| DiagnosticRelatedInformation {
+            location: Location {
+                uri: uri.clone(),
+                range: index.range(label.range),
+            },
+            message: label.message,
+        })
+        .collect::<Vec<_>>();
+
+    Diagnostic {
+        range: primary.map_or_else(lsp_types::Range::default, |label| index.range(label.range)),
+        severity: Some(match diag.severity {
+            codespan::Severity::Bug | codespan::Severity::Error => DiagnosticSeverity::ERROR,
+            codespan::Severity::Warning => DiagnosticSeverity::WARNING,
+            codespan::Severity::Note => DiagnosticSeverity::INFORMATION,
+            codespan::Severity::Help => DiagnosticSeverity::HINT,
+        }),
+        code: diag.code.map(NumberOrString::String),
+        source: Some("rotur-icn".into()),
+        message,
+        related_information: (!related_information.is_empty()).then_some(related_information),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{DiagnosticSeverity, NumberOrString, Position, Range, Uri};
+
+    use super::check;
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+        Range {
+            start: Position {
+                line: start.0,
+                character: start.1,
+            },
+            end: Position {
+                line: end.0,
+                character: end.1,
+            },
+        }
+    }
+
+    #[test]
+    fn diagnostics_are_placed_by_lines_and_columns() {
+        let uri: Uri = "file:///test.icn".parse().expect("the URI is valid");
+        let diagnostics = check(&uri, "dot 0 0\n  w 0\n");
+
+        let [diagnostic] = diagnostics.as_slice() else {
+            panic!("a single diagnostic should be found, not {diagnostics:?}");
+        };
+
+        assert_eq!(diagnostic.range, range((1, 2), (1, 3)));
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostic.code, Some(NumberOrString::String("WL00".into())));
+    }
+
+    #[test]
+    fn errors_are_reported_as_errors() {
+        let uri: Uri = "file:///test.icn".parse().expect("the URI is valid");
+        let diagnostics = check(&uri, "dot 0 0\nlien 0 0 1 1");
+
+        assert!(diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == Some(DiagnosticSeverity::ERROR)
+                && diagnostic.range == range((1, 0), (1, 4))
+        }));
+    }
+}
@@ -0,0 +1,20 @@
+use lsp_server::Connection;
+
+use crate::server::{Error, Server};
+
+mod commands;
+mod diagnostics;
+mod position;
+mod server;
+
+fn main() -> Result<(), Error> {
+    let (connection, io_threads) = Connection::stdio();
+
+    connection.initialize(serde_json::to_value(server::capabilities())?)?;
+    Server::default().run(&connection)?;
+
+    drop(connection);
+    io_threads.join()?;
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use lsp_types::Position;
+
+/// Converts between byte offsets & LSP positions (lines & UTF-16 columns)
+pub struct LineIndex<'s> {
+    src: &'s str,
+    line_starts: Vec<usize>,
+}
+
+impl<'s> LineIndex<'s> {
+    pub fn new(src: &'s str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(src.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self { src, line_starts }
+    }
+
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "LSP positions are limited to u32"
+    )]
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.src.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+
+        Position {
+            line: line as u32,
+            character: self.src[line_start..offset].encode_utf16().count() as u32,
+        }
+    }
+
+    pub fn offset(&self, position: Position) -> usize {
+        let Some(&line_start) = self.line_starts.get(position.line as usize) else {
+            return self.src.len();
+        };
+
+        let mut character = 0;
+
+        for (i, ch) in self.src[line_start..].char_indices() {
+            if character >= position.character as usize || ch == '\n' {
+                return line_start + i;
+            }
+
+            character += ch.len_utf16();
+        }
+
+        self.src.len()
+    }
+
+    pub fn range(&self, range: std::ops::Range<usize>) -> lsp_types::Range {
+        lsp_types::Range {
+            start: self.position(range.start),
+            end: self.position(range.end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Position;
+
+    use super::LineIndex;
+
+    const SRC: &str = "dot 0 0\na😀b\n\nline";
+
+    fn position(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn positions_round_trip_through_offsets() {
+        let index = LineIndex::new(SRC);
+
+        for (offset, _) in SRC.char_indices().chain([(SRC.len(), ' ')]) {
+            assert_eq!(index.offset(index.position(offset)), offset);
+        }
+    }
+
+    #[test]
+    fn columns_count_utf16_units() {
+        let index = LineIndex::new(SRC);
+        let b = SRC.find('b').expect("the source has a `b`");
+
+        // the emoji is outside of the BMP, so it takes a surrogate pair
+        assert_eq!(index.position(b), position(1, 3));
+        assert_eq!(index.offset(position(1, 3)), b);
+
+        // within the pair, the offset stays on a char boundary past it
+        assert_eq!(index.offset(position(1, 2)), b);
+    }
+
+    #[test]
+    fn positions_past_the_end_are_clamped() {
+        let index = LineIndex::new(SRC);
+        let first_newline = SRC.find('\n').expect("the source has lines");
+        let empty_line = SRC.find("\n\n").expect("the source has an empty line") + 1;
+
+        assert_eq!(index.offset(position(0, 100)), first_newline);
+        assert_eq!(index.offset(position(2, 1)), empty_line);
+        assert_eq!(index.offset(position(3, 100)), SRC.len());
+        assert_eq!(index.offset(position(100, 0)), SRC.len());
+
+        assert_eq!(index.position(SRC.len() + 10), position(3, 4));
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionResponse,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    Documentation, Hover, HoverContents, HoverParams, HoverProviderCapability, MarkupContent,
+    MarkupKind, PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind,
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+        PublishDiagnostics,
+    },
+    request::{Completion, HoverRequest, Request as _},
+};
+
+use crate::{
+    commands::{COMMANDS, CommandInfo},
+    diagnostics,
+    position::LineIndex,
+};
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions::default()),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..Default::default()
+    }
+}
+
+/// Open documents, by their URIs
+#[derive(Default)]
+pub struct Server {
+    documents: HashMap<String, String>,
+}
+
+impl Server {
+    /// Handle messages until the client shuts the server down
+    pub fn run(&mut self, connection: &Connection) -> Result<(), Error> {
+        for msg in &connection.receiver {
+            match msg {
+                Message::Request(req) => {
+                    if connection.handle_shutdown(&req)? {
+                        return Ok(());
+                    }
+
+                    connection
+                        .sender
+                        .send(Message::Response(self.handle_request(req)))?;
+                }
+                Message::Notification(not) => {
+                    if let Some(params) = self.handle_notification(not) {
+                        connection
+                            .sender
+                            .send(Message::Notification(Notification::new(
+                                PublishDiagnostics::METHOD.into(),
+                                params,
+                            )))?;
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&self, req: Request) -> Response {
+        match req.method.as_str() {
+            Completion::METHOD => Response::new_ok(req.id, completion()),
+            HoverRequest::METHOD => match serde_json::from_value(req.params) {
+                Ok(params) => Response::new_ok(req.id, self.hover(&params)),
+                Err(err) => {
+                    Response::new_err(req.id, ErrorCode::InvalidParams as i32, err.to_string())
+                }
+            },
+            _ => Response::new_err(
+                req.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unknown request: {}", req.method),
+            ),
+        }
+    }
+
+    /// Track document changes, returning the diagnostics to publish
+    fn handle_notification(&mut self, not: Notification) -> Option<PublishDiagnosticsParams> {
+        let (uri, version) = match not.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = not
+                    .extract::<DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)
+                    .ok()?;
+                let doc = params.text_document;
+
+                self.documents.insert(doc.uri.as_str().into(), doc.text);
+                (doc.uri, Some(doc.version))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = not
+                    .extract::<DidChangeTextDocumentParams>(DidChangeTextDocument::METHOD)
+                    .ok()?;
+                let doc = params.text_document;
+
+                // with full syncing, the last change holds the whole document
+                let text = params.content_changes.into_iter().next_back()?.text;
+                self.documents.insert(doc.uri.as_str().into(), text);
+                (doc.uri, Some(doc.version))
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = not
+                    .extract::<DidCloseTextDocumentParams>(DidCloseTextDocument::METHOD)
+                    .ok()?;
+                let uri = params.text_document.uri;
+
+                self.documents.remove(uri.as_str());
+
+                // clear the diagnostics of the closed document
+                return Some(PublishDiagnosticsParams {
+                    uri,
+                    diagnostics: Vec::new(),
+                    version: None,
+                });
+            }
+            _ => return None,
+        };
+
+        let src = self.documents.get(uri.as_str())?;
+
+        Some(PublishDiagnosticsParams {
+            diagnostics: diagnostics::check(&uri, src),
+            uri,
+            version,
+        })
+    }
+
+    /// Show the signature of the command under the cursor
+    fn hover(&self, params: &HoverParams) -> Option<Hover> {
+        let pos = &params.text_document_position_params;
+        let src = self.documents.get(pos.text_document.uri.as_str())?;
+
+        let index = LineIndex::new(src);
+        let offset = index.offset(pos.position);
+
+        let mut lexing_errors = Vec::new();
+        let (icon, _) = rotur_icn_parser::parse(rotur_icn_lexer::lex(&mut lexing_errors, src));
+
+        let within = |(start, end): rotur_icn_lexer::token::Pos| {
+            start.byte_idx <= offset && offset <= end.byte_idx
+        };
+
+        let cmd = icon.commands.iter().find(|cmd| {
+            let end = cmd.args.last().map_or(cmd.name_pos.1, |arg| arg.pos.1);
+            within((cmd.name_pos.0, end))
+        })?;
+
+        let info = CommandInfo::find(cmd.name)?;
+
+        let arg_note = cmd
+            .args
+            .iter()
+            .position(|arg| within(arg.pos))
+            .and_then(|i| info.args.get(i))
+            .map_or_else(String::new, |arg| format!("\n\nargument `{arg}`"));
+
+        let value = format!("```\n{}\n```\n{}{arg_note}", info.signature(), info.doc);
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(index.range(cmd.name_pos.0.byte_idx..cmd.name_pos.1.byte_idx)),
+        })
+    }
+}
+
+fn completion() -> CompletionResponse {
+    CompletionResponse::Array(
+        COMMANDS
+            .iter()
+            .map(|cmd| CompletionItem {
+                label: cmd.name.into(),
+                kind: Some(CompletionItemKind::FUNCTION),
+                detail: Some(cmd.signature()),
+                documentation: Some(Documentation::String(cmd.doc.into())),
+                ..Default::default()
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{
+        HoverContents, HoverParams, Position, TextDocumentIdentifier, TextDocumentPositionParams,
+        Uri,
+    };
+
+    use super::{Server, completion};
+    use crate::commands::COMMANDS;
+
+    const URI: &str = "file:///test.icn";
+    const SRC: &str = "c #f00\nline 0 0 10 10\n";
+
+    fn server() -> Server {
+        let mut server = Server::default();
+        server.documents.insert(URI.into(), SRC.into());
+        server
+    }
+
+    /// The hover's text & the line & columns it spans
+    fn hover(server: &Server, line: u32, character: u32) -> Option<(String, (u32, u32, u32))> {
+        let params = HoverParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: URI.parse::<Uri>().expect("the URI is valid"),
+                },
+                position: Position { line, character },
+            },
+            work_done_progress_params: lsp_types::WorkDoneProgressParams::default(),
+        };
+
+        let hover = server.hover(&params)?;
+        let HoverContents::Markup(contents) = hover.contents else {
+            panic!("the hover should be markup");
+        };
+        let range = hover.range.expect("the hover spans the command's name");
+
+        Some((
+            contents.value,
+            (range.start.line, range.start.character, range.end.character),
+        ))
+    }
+
+    #[test]
+    fn hover_shows_the_command_under_the_cursor() {
+        let (value, range) = hover(&server(), 1, 2).expect("the cursor is on `line`");
+
+        assert!(value.contains("line x1 y1 x2 y2"), "{value}");
+        assert!(!value.contains("argument"), "{value}");
+        assert_eq!(range, (1, 0, 4));
+    }
+
+    #[test]
+    fn hover_names_the_argument_under_the_cursor() {
+        let (value, range) = hover(&server(), 1, 10).expect("the cursor is on `10`");
+
+        assert!(value.contains("argument `x2`"), "{value}");
+        assert_eq!(range, (1, 0, 4));
+
+        let (value, _) = hover(&server(), 0, 3).expect("the cursor is on `#f00`");
+        assert!(value.contains("argument `colour`"), "{value}");
+    }
+
+    #[test]
+    fn hover_finds_nothing_outside_of_commands() {
+        assert!(hover(&server(), 2, 0).is_none());
+    }
+
+    #[test]
+    fn completion_offers_every_command() {
+        let lsp_types::CompletionResponse::Array(items) = completion() else {
+            panic!("the completion should be an array");
+        };
+
+        assert_eq!(items.len(), COMMANDS.len());
+        assert!(items.iter().any(|item| item.label == "cutcircle"));
+    }
+}
@@ -0,0 +1,139 @@
+use rotur_icn_lowerer::hir;
+use rotur_icn_resolver::{Compat, Resolver, lir};
+use rotur_icn_units::Vector;
+
+/// Rebuild the operations, emitting state changes only right before they're needed
+///
+/// The resolver is run over both the input & the output,
+/// so that every kept shape is drawn with the same state as before.
+pub fn fold(
+    ops: &[hir::Operation],
+    elements: &[Option<lir::Element>],
+    occluded: &[bool],
+    compat: Compat,
+) -> hir::IconHir {
+    let mut folder = Folder {
+        operations: Vec::new(),
+        output: Resolver::new(compat),
+        errors: Vec::new(),
+    };
+
+    let mut input = Resolver::new(compat);
+    let mut errors = Vec::new();
+
+    let mut pending_width = None;
+    let mut pending_colour = None;
+    let mut pending_moves = Vec::new();
+
+    for (op_i, op) in ops.iter().enumerate() {
+        input.step(&mut errors, op_i, op);
+
+        match &op.kind {
+            hir::OperationKind::SetWidth(_) => {
+                pending_width = Some(op);
+                continue;
+            }
+            hir::OperationKind::SetColour(_) => {
+                pending_colour = Some(op);
+                continue;
+            }
+            hir::OperationKind::MoveCentre(_) | hir::OperationKind::ResetCentre(_) => {
+                pending_moves.push(op);
+                continue;
+            }
+            _ => {}
+        }
+
+        // dangling continued lines draw nothing
+        if elements[op_i].is_none() || occluded[op_i] {
+            continue;
+        }
+
+        if folder.output.width.to_bits() != input.width.to_bits()
+            && let Some(op) = pending_width
+        {
+            folder.emit(op.clone());
+        }
+
+        if folder.output.colour != input.colour
+            && let Some(op) = pending_colour
+        {
+            folder.emit(op.clone());
+        }
+
+        if folder.output.origin != input.origin {
+            folder.move_centre(input.origin, &pending_moves);
+        }
+
+        pending_moves.clear();
+
+        folder.draw(op);
+    }
+
+    hir::IconHir {
+        operations: folder.operations,
+    }
+}
+
+struct Folder {
+    operations: Vec<hir::Operation>,
+    output: Resolver,
+    errors: Vec<rotur_icn_resolver::Error>,
+}
+
+impl Folder {
+    fn emit(&mut self, op: hir::Operation) {
+        self.output
+            .step(&mut self.errors, self.operations.len(), &op);
+        self.operations.push(op);
+    }
+
+    /// Move the output's centre to the origin, folding the moves into one, if it's exact
+    fn move_centre(&mut self, origin: Vector, pending_moves: &[&hir::Operation]) {
+        let Some(last_move) = pending_moves.last() else {
+            return;
+        };
+
+        let change = origin - self.output.origin;
+
+        if origin == Vector::ZERO {
+            self.emit(hir::Operation {
+                cmd_pos: last_move.cmd_pos,
+                kind: hir::OperationKind::ResetCentre(hir::ResetCentre),
+            });
+        } else if self.output.origin + change == origin {
+            self.emit(hir::Operation {
+                cmd_pos: last_move.cmd_pos,
+                kind: hir::OperationKind::MoveCentre(hir::MoveCentre { change }),
+            });
+        } else {
+            for &op in pending_moves {
+                self.emit(op.clone());
+            }
+        }
+    }
+
+    /// Emit a drawing operation, continuing the line, if it starts at the last point
+    fn draw(&mut self, op: &hir::Operation) {
+        if let hir::OperationKind::DrawLine(draw_line) = &op.kind
+            && self.output.last_point.is_some()
+        {
+            let cont = hir::Operation {
+                cmd_pos: op.cmd_pos,
+                kind: hir::OperationKind::ContinueLine(hir::ContinueLine {
+                    next: draw_line.end,
+                }),
+            };
+
+            // where a continued line starts depends on the semantics, so it's left to the resolver
+            let draws = |op| self.output.clone().step(&mut Vec::new(), 0, op);
+
+            if draws(&cont) == draws(op) {
+                self.emit(cont);
+                return;
+            }
+        }
+
+        self.emit(op.clone());
+    }
+}
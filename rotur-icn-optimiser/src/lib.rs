@@ -0,0 +1,36 @@
+use rotur_icn_lowerer::hir;
+use rotur_icn_resolver::{Compat, Resolver, lir};
+
+mod folding;
+mod occlusion;
+
+/// Shrink the ICN, without changing how it renders
+///
+/// - state changes which affect nothing are removed
+/// - chains of centre moves are folded, when it's exact
+/// - lines starting at the last point are turned into continued lines
+/// - shapes fully covered by a later opaque filled rectangle are dropped
+/// - dangling continued lines, which draw nothing, are dropped
+pub fn optimise(icon: &hir::IconHir) -> hir::IconHir {
+    optimise_with(icon, Compat::default())
+}
+
+/// Shrink the ICN, without changing how it renders with the semantics
+pub fn optimise_with(icon: &hir::IconHir, compat: Compat) -> hir::IconHir {
+    let elements = resolve_each(icon, compat);
+    let occluded = occlusion::find_occluded(&icon.operations, &elements);
+
+    folding::fold(&icon.operations, &elements, &occluded, compat)
+}
+
+/// Resolve the ICN, keeping the elements aligned with their operations
+fn resolve_each(icon: &hir::IconHir, compat: Compat) -> Vec<Option<lir::Element>> {
+    let mut resolver = Resolver::new(compat);
+    let mut errors = Vec::new();
+
+    icon.operations
+        .iter()
+        .enumerate()
+        .map(|(op_i, op)| resolver.step(&mut errors, op_i, op))
+        .collect()
+}
@@ -0,0 +1,69 @@
+use rotur_icn_lowerer::hir;
+use rotur_icn_resolver::lir;
+use rotur_icn_units::{Number, Vector};
+
+/// Part of a cover's size, by which it's shrunk to stay clear of rounding at its edges
+const COVER_MARGIN: Number = 1e-4;
+
+/// Find elements which are fully covered by later ones
+///
+/// Elements, whose last point is continued from, are always kept.
+pub fn find_occluded(ops: &[hir::Operation], elements: &[Option<lir::Element>]) -> Vec<bool> {
+    let mut occluded = vec![false; ops.len()];
+
+    // areas of opaque filled rectangles drawn after the current element
+    let mut covers: Vec<(Vector, Vector)> = Vec::new();
+    let mut next_continues = false;
+
+    for (op_i, (op, el)) in ops.iter().zip(elements).enumerate().rev() {
+        let Some(el) = el else {
+            continue;
+        };
+
+        let bounds = get_bounds(el);
+
+        occluded[op_i] = !next_continues && covers.iter().any(|&cover| contains(cover, bounds));
+
+        next_continues = matches!(op.kind, hir::OperationKind::ContinueLine(_));
+
+        if let Some(cover) = get_cover(el) {
+            covers.push(cover);
+        }
+    }
+
+    occluded
+}
+
+/// Bounds of every pixel the element may draw
+fn get_bounds(el: &lir::Element) -> (Vector, Vector) {
+    match &el.kind {
+        // the CPU renderer compares disks' squared distance to their radius
+        lir::ElementKind::Disk(disk) => {
+            let radius = disk.radius.max(disk.radius.sqrt());
+            (disk.centre - radius, disk.centre + radius)
+        }
+        _ => el.bounds(),
+    }
+}
+
+/// Area, which the element paints over fully
+fn get_cover(el: &lir::Element) -> Option<(Vector, Vector)> {
+    let lir::ElementKind::Rectangle(rect) = &el.kind else {
+        return None;
+    };
+
+    if !rect.filled || el.colour.a != u8::MAX || rect.sizes.x < 0. || rect.sizes.y < 0. {
+        return None;
+    }
+
+    let margin = rect.sizes * COVER_MARGIN + COVER_MARGIN;
+
+    Some((
+        rect.bottom_left + margin,
+        rect.bottom_left + rect.sizes - margin,
+    ))
+}
+
+fn contains(cover: (Vector, Vector), bounds: (Vector, Vector)) -> bool {
+    bounds.0.within(cover) && bounds.1.within(cover)
+}
@@ -0,0 +1,67 @@
+use rand::{SeedableRng as _, distr::Distribution as _, rngs::StdRng};
+
+use rotur_icn_lowerer::hir;
+use rotur_icn_optimiser::{optimise, optimise_with};
+use rotur_icn_rand::IcnSampler;
+use rotur_icn_renderer::{cpu::Renderer, fitter};
+use rotur_icn_resolver::Compat;
+use rotur_icn_units::{Colour, Number};
+
+const ICONS: usize = 64;
+const SCALE: Number = 4.;
+
+fn render(icon: &hir::IconHir, compat: Compat) -> Vec<u8> {
+    let (icon, _) = rotur_icn_resolver::resolve_with(icon, compat);
+    let fitted = fitter::fit(&icon);
+
+    let mut renderer = Renderer::new(fitted.size + 2., SCALE, fitted.camera, Colour::ZERO);
+    renderer.load(&icon);
+
+    let (mut buf, _) = renderer.new_buf();
+    renderer.render(&mut buf);
+
+    buf
+}
+
+fn check_renders(compat: Compat) {
+    let sampler = IcnSampler::default();
+    let mut rng = StdRng::seed_from_u64(0x1c4);
+
+    for _ in 0..ICONS {
+        let icon: hir::IconHir = sampler.sample(&mut rng);
+        let optimised = optimise_with(&icon, compat);
+
+        assert!(
+            render(&icon, compat) == render(&optimised, compat),
+            "renders differ\n{icon}\noptimised into\n{optimised}"
+        );
+    }
+}
+
+#[test]
+fn optimised_icns_render_the_same() {
+    check_renders(Compat::default());
+}
+
+#[test]
+fn optimised_icns_render_the_same_with_other_semantics() {
+    check_renders(Compat {
+        default_width: 2.,
+        arc_direction_scale: 1.,
+        offset_continued_lines: false,
+        ..Compat::default()
+    });
+}
+
+#[test]
+fn optimised_icns_are_no_longer() {
+    let sampler = IcnSampler::default();
+    let mut rng = StdRng::seed_from_u64(0x1c4);
+
+    for _ in 0..ICONS {
+        let icon: hir::IconHir = sampler.sample(&mut rng);
+        let optimised = optimise(&icon);
+
+        assert!(optimised.operations.len() <= icon.operations.len());
+    }
+}
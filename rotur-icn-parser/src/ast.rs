@@ -3,19 +3,46 @@ use arrayvec::ArrayVec;
 use rotur_icn_lexer::token;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Icon<'s> {
+    /// Trivia before the first command
+    pub trivia: Vec<Trivia<'s>>,
     pub commands: Vec<Command<'s>>,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Command<'s> {
     pub name: &'s str,
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "rotur_icn_lexer::serde_pos::serialize")
+    )]
     pub name_pos: token::Pos,
     pub args: ArrayVec<Argument, 6>,
+    /// Trivia after the command's name, up to the next command
+    pub trivia: Vec<Trivia<'s>>,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Argument {
     pub lit: token::Literal,
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "rotur_icn_lexer::serde_pos::serialize")
+    )]
+    pub pos: token::Pos,
+}
+
+/// Only parsed in a dialect with comments
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Trivia<'s> {
+    pub value: token::Trivia<'s>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "rotur_icn_lexer::serde_pos::serialize")
+    )]
     pub pos: token::Pos,
 }
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use rotur_icn_lexer::{display::PosDisplay, token};
+use rotur_icn_lexer::{Suggestion, display::PosDisplay, token};
 
 #[derive(Debug, Clone)]
 pub enum Error {
@@ -53,6 +53,13 @@ impl Error {
         }
     }
 
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        match self {
+            Self::TooManyArguments { overflow_pos, .. } => Some(Suggestion::remove(*overflow_pos)),
+            Self::StrandedArguments { stranded_pos } => Some(Suggestion::remove(*stranded_pos)),
+        }
+    }
+
     pub fn help(&self) -> &'static str {
         match self {
             Self::TooManyArguments { .. } => {
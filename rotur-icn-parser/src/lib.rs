@@ -1,5 +1,5 @@
 use arrayvec::ArrayVec;
-use ast::{Argument, Command, Icon};
+use ast::{Argument, Command, Icon, Trivia};
 
 pub mod ast;
 mod display;
@@ -9,13 +9,16 @@ pub use error::Error;
 
 use rotur_icn_lexer::token;
 
+#[expect(clippy::too_many_lines, reason = "a single pass over every token")]
 pub fn parse<'s>(tokens: impl Iterator<Item = token::PToken<'s>>) -> (Icon<'s>, Vec<Error>) {
     let mut errors = Vec::new();
 
+    let mut leading_trivia = Vec::new();
     let mut commands = Vec::new();
 
     let mut command: Option<(&'s str, token::Pos)> = None;
     let mut arguments = ArrayVec::new();
+    let mut trivia = Vec::new();
     let mut is_capturing_error = false;
     let mut is_capturing_overflow = false;
     let mut err_l_loc = None;
@@ -24,17 +27,12 @@ pub fn parse<'s>(tokens: impl Iterator<Item = token::PToken<'s>>) -> (Icon<'s>,
         match token {
             token::Token::Identifier(ident) => {
                 if let Some(err_l_loc) = err_l_loc.take() {
-                    if is_capturing_overflow {
-                        errors.push(Error::TooManyArguments {
-                            #[expect(clippy::missing_panics_doc, reason = "for bug catching")]
-                            keyword_pos: command.expect("they shouldn't be stranded, as no capture happens during stranded handling").1,
-                            overflow_pos: (err_l_loc, prev_r_loc),
-                        });
-                    } else {
-                        errors.push(Error::StrandedArguments {
-                            stranded_pos: (err_l_loc, prev_r_loc),
-                        });
-                    }
+                    push_captured_error(
+                        &mut errors,
+                        (err_l_loc, prev_r_loc),
+                        is_capturing_overflow,
+                        command,
+                    );
 
                     is_capturing_error = false;
                 }
@@ -44,6 +42,7 @@ pub fn parse<'s>(tokens: impl Iterator<Item = token::PToken<'s>>) -> (Icon<'s>,
                         name: cmd,
                         name_pos: cmd_pos,
                         args: std::mem::take(&mut arguments),
+                        trivia: std::mem::take(&mut trivia),
                     });
                 }
 
@@ -55,17 +54,20 @@ pub fn parse<'s>(tokens: impl Iterator<Item = token::PToken<'s>>) -> (Icon<'s>,
                 command = Some((ident.value, (l, r)));
             }
             token::Token::Literal(lit) => {
-                if command.is_none() && !is_capturing_error {
-                    #[expect(clippy::missing_panics_doc, reason = "for bug catching")]
-                    {
-                        assert!(
-                            err_l_loc.is_none(),
-                            "no other error should be getting captured atm"
-                        );
+                if command.is_none() {
+                    // the following stranded arguments are captured along, not kept
+                    if !is_capturing_error {
+                        #[expect(clippy::missing_panics_doc, reason = "for bug catching")]
+                        {
+                            assert!(
+                                err_l_loc.is_none(),
+                                "no other error should be getting captured atm"
+                            );
+                        }
+                        err_l_loc = Some(l);
+                        is_capturing_overflow = false;
+                        is_capturing_error = true;
                     }
-                    err_l_loc = Some(l);
-                    is_capturing_overflow = false;
-                    is_capturing_error = true;
                 } else {
                     let push_res = arguments.try_push(Argument { lit, pos: (l, r) });
                     if push_res.is_err() && !is_capturing_error {
@@ -82,18 +84,69 @@ pub fn parse<'s>(tokens: impl Iterator<Item = token::PToken<'s>>) -> (Icon<'s>,
                     }
                 }
             }
+            token::Token::Trivia(value) => {
+                let buf = if command.is_some() {
+                    &mut trivia
+                } else {
+                    &mut leading_trivia
+                };
+
+                buf.push(Trivia { value, pos: (l, r) });
+
+                // so that captured errors don't span it
+                continue;
+            }
         }
 
         prev_r_loc = r;
     }
 
+    // the error may still be getting captured at the end of input
+    if let Some(err_l_loc) = err_l_loc {
+        push_captured_error(
+            &mut errors,
+            (err_l_loc, prev_r_loc),
+            is_capturing_overflow,
+            command,
+        );
+    }
+
     if let Some((cmd, cmd_pos)) = command {
         commands.push(Command {
             name: cmd,
             name_pos: cmd_pos,
             args: arguments,
+            trivia,
         });
     }
 
-    (Icon { commands }, errors)
+    (
+        Icon {
+            trivia: leading_trivia,
+            commands,
+        },
+        errors,
+    )
+}
+
+fn push_captured_error(
+    errors: &mut Vec<Error>,
+    err_pos: token::Pos,
+    is_capturing_overflow: bool,
+    command: Option<(&str, token::Pos)>,
+) {
+    if is_capturing_overflow {
+        errors.push(Error::TooManyArguments {
+            keyword_pos: command
+                .expect(
+                    "they shouldn't be stranded, as no capture happens during stranded handling",
+                )
+                .1,
+            overflow_pos: err_pos,
+        });
+    } else {
+        errors.push(Error::StrandedArguments {
+            stranded_pos: err_pos,
+        });
+    }
 }
@@ -2,32 +2,44 @@ use std::ops::Range;
 
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 
-use crate::Errors;
+use crate::{Errors, Suggestion};
 
 impl Errors {
     pub fn into_diagnostics(&self) -> impl Iterator<Item = Diagnostic<()>> {
-        self.resolving
+        self.linting
             .iter()
-            .map(|err| ResolverErrorDiagnostic(err).into())
+            .map(|warning| LinterWarningDiagnostic(warning).into())
             .chain(
-                self.lowering
-                    .iter()
-                    .map(|err| LowererErrorDiagnostic(err).into()),
+                self.resolving.iter().map(|err| {
+                    with_suggestion(ResolverErrorDiagnostic(err).into(), err.suggestion())
+                }),
             )
             .chain(
-                self.parsing
-                    .iter()
-                    .map(|err| ParserErrorDiagnostic(err).into()),
+                self.lowering.iter().map(|err| {
+                    with_suggestion(LowererErrorDiagnostic(err).into(), err.suggestion())
+                }),
+            )
+            .chain(
+                self.parsing.iter().map(|err| {
+                    with_suggestion(ParserErrorDiagnostic(err).into(), err.suggestion())
+                }),
             )
             .chain(
                 self.lexing
                     .iter()
-                    .map(|err| LexerErrorDiagnostic(err).into()),
+                    .map(|err| with_suggestion(LexerErrorDiagnostic(err).into(), err.suggestion())),
             )
     }
 }
 
-struct LexerErrorDiagnostic<'err>(pub &'err rotur_icn_lexer::Error);
+fn with_suggestion(diagnostic: Diagnostic<()>, suggestion: Option<Suggestion>) -> Diagnostic<()> {
+    match suggestion {
+        Some(suggestion) => diagnostic.with_note(format!("suggestion: {suggestion}")),
+        None => diagnostic,
+    }
+}
+
+pub(crate) struct LexerErrorDiagnostic<'err>(pub &'err rotur_icn_lexer::Error);
 
 impl From<LexerErrorDiagnostic<'_>> for Diagnostic<()> {
     fn from(LexerErrorDiagnostic(error): LexerErrorDiagnostic) -> Self {
@@ -41,7 +53,7 @@ impl From<LexerErrorDiagnostic<'_>> for Diagnostic<()> {
     }
 }
 
-struct ParserErrorDiagnostic<'err>(pub &'err rotur_icn_parser::Error);
+pub(crate) struct ParserErrorDiagnostic<'err>(pub &'err rotur_icn_parser::Error);
 
 impl From<ParserErrorDiagnostic<'_>> for Diagnostic<()> {
     fn from(ParserErrorDiagnostic(error): ParserErrorDiagnostic) -> Self {
@@ -68,7 +80,7 @@ impl From<ParserErrorDiagnostic<'_>> for Diagnostic<()> {
     }
 }
 
-struct LowererErrorDiagnostic<'err>(pub &'err rotur_icn_lowerer::Error);
+pub(crate) struct LowererErrorDiagnostic<'err>(pub &'err rotur_icn_lowerer::Error);
 
 impl From<LowererErrorDiagnostic<'_>> for Diagnostic<()> {
     fn from(LowererErrorDiagnostic(error): LowererErrorDiagnostic) -> Self {
@@ -82,7 +94,7 @@ impl From<LowererErrorDiagnostic<'_>> for Diagnostic<()> {
                 .with_message(error_kind)
                 .with_labels_iter([
                     Label::primary((), LexerPosRange(overflow_pos))
-                        .with_message(error_kind.help().unwrap_or("")),
+                        .with_message(error_kind.help().unwrap_or_default()),
                     Label::secondary((), LexerPosRange(&error.cmd_pos))
                         .with_message("while lowering this command"),
                 ]),
@@ -95,7 +107,7 @@ impl From<LowererErrorDiagnostic<'_>> for Diagnostic<()> {
                 .with_message(error_kind)
                 .with_labels_iter([
                     Label::primary((), LexerLocRange(args_end_loc))
-                        .with_message(error_kind.help().unwrap_or("")),
+                        .with_message(error_kind.help().unwrap_or_default()),
                     Label::secondary((), LexerPosRange(&error.cmd_pos))
                         .with_message("while lowering this command"),
                 ]),
@@ -112,6 +124,7 @@ impl From<LowererErrorDiagnostic<'_>> for Diagnostic<()> {
             | rotur_icn_lowerer::ErrorKind::ArgOutOfRange {
                 arg_pos,
                 arg_index: _,
+                value: _,
                 range_start: _,
                 range_end: _,
             }) => Self::error()
@@ -119,20 +132,39 @@ impl From<LowererErrorDiagnostic<'_>> for Diagnostic<()> {
                 .with_message(error_kind)
                 .with_labels_iter([
                     Label::primary((), LexerPosRange(arg_pos))
-                        .with_message(error_kind.help().unwrap_or("")),
+                        .with_message(error_kind.help().unwrap_or_default()),
                     Label::secondary((), LexerPosRange(&error.cmd_pos))
                         .with_message("while lowering this command"),
                 ]),
-            error_kind @ rotur_icn_lowerer::ErrorKind::InvalidCommand => Self::error()
-                .with_code(error_kind.code())
-                .with_message(error_kind)
-                .with_labels_iter([Label::primary((), LexerPosRange(&error.cmd_pos))
-                    .with_message(error_kind.help().unwrap_or(""))]),
+            error_kind @ rotur_icn_lowerer::ErrorKind::InvalidCommand {
+                alias_of, similar, ..
+            } => {
+                // the first one is already in the help
+                let others = alias_of
+                    .map(|alias| alias.command)
+                    .into_iter()
+                    .chain(similar.iter().copied())
+                    .skip(1)
+                    .map(|known| {
+                        Label::secondary((), LexerPosRange(&error.cmd_pos))
+                            .with_message(format!("or `{known}`?"))
+                    });
+
+                Self::error()
+                    .with_code(error_kind.code())
+                    .with_message(error_kind)
+                    .with_labels_iter(
+                        [Label::primary((), LexerPosRange(&error.cmd_pos))
+                            .with_message(error_kind.help().unwrap_or_default())]
+                        .into_iter()
+                        .chain(others),
+                    )
+            }
         }
     }
 }
 
-struct ResolverErrorDiagnostic<'err>(pub &'err rotur_icn_resolver::Error);
+pub(crate) struct ResolverErrorDiagnostic<'err>(pub &'err rotur_icn_resolver::Error);
 
 impl From<ResolverErrorDiagnostic<'_>> for Diagnostic<()> {
     fn from(ResolverErrorDiagnostic(error): ResolverErrorDiagnostic<'_>) -> Self {
@@ -146,6 +178,36 @@ impl From<ResolverErrorDiagnostic<'_>> for Diagnostic<()> {
     }
 }
 
+pub(crate) struct LinterWarningDiagnostic<'w>(pub &'w rotur_icn_linter::Warning);
+
+impl From<LinterWarningDiagnostic<'_>> for Diagnostic<()> {
+    fn from(LinterWarningDiagnostic(warning): LinterWarningDiagnostic<'_>) -> Self {
+        let diagnostic = if warning.is_denied() {
+            Self::error()
+        } else {
+            Self::warning()
+        };
+
+        let primary = Label::primary((), LexerPosRange(&warning.cmd_pos))
+            .with_message(warning.kind.help().unwrap_or(""));
+
+        let diagnostic = diagnostic
+            .with_code(warning.kind.code())
+            .with_message(&warning.kind)
+            .with_label(primary);
+
+        match &warning.kind {
+            rotur_icn_linter::WarningKind::UnusedColour {
+                overridden_pos: Some(overridden_pos),
+            } => diagnostic.with_label(
+                Label::secondary((), LexerPosRange(overridden_pos))
+                    .with_message("replaced by this colour"),
+            ),
+            _ => diagnostic,
+        }
+    }
+}
+
 struct LexerPosRange<'p>(&'p rotur_icn_lexer::token::Pos);
 
 impl From<LexerPosRange<'_>> for Range<usize> {
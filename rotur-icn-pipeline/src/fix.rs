@@ -0,0 +1,60 @@
+use rotur_icn_lexer::Suggestion;
+
+use crate::Errors;
+
+impl Errors {
+    /// Suggested fixes of the errors, in the order of the stages
+    ///
+    /// Lints have no suggestions, as they aren't errors to begin with.
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        self.lexing
+            .iter()
+            .filter_map(rotur_icn_lexer::Error::suggestion)
+            .chain(
+                self.parsing
+                    .iter()
+                    .filter_map(rotur_icn_parser::Error::suggestion),
+            )
+            .chain(
+                self.lowering
+                    .iter()
+                    .filter_map(rotur_icn_lowerer::Error::suggestion),
+            )
+            .chain(
+                self.resolving
+                    .iter()
+                    .filter_map(rotur_icn_resolver::Error::suggestion),
+            )
+            .collect()
+    }
+}
+
+/// Apply the suggestions to the source, returning it with the number of the applied ones
+///
+/// A suggestion overlapping an earlier one is skipped,
+/// as it may no longer make sense; processing the result again finds it anew, if so.
+pub fn apply_suggestions(src: &str, suggestions: &[Suggestion]) -> (String, usize) {
+    let mut sorted = suggestions.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|suggestion| (suggestion.range().start, suggestion.range().end));
+
+    let mut fixed = String::with_capacity(src.len());
+    let mut copied_to = 0;
+    let mut applied = 0;
+
+    for suggestion in sorted {
+        let range = suggestion.range();
+
+        if range.start < copied_to {
+            continue;
+        }
+
+        fixed.push_str(&src[copied_to..range.start]);
+        fixed.push_str(&suggestion.replacement);
+        copied_to = range.end;
+        applied += 1;
+    }
+
+    fixed.push_str(&src[copied_to..]);
+
+    (fixed, applied)
+}
@@ -0,0 +1,506 @@
+use std::ops::Range;
+
+use arrayvec::ArrayVec;
+
+use rotur_icn_lexer::{
+    self as lexer, Dialect,
+    token::{self, Loc},
+};
+use rotur_icn_linter as linter;
+use rotur_icn_lowerer::{self as lowerer, hir};
+use rotur_icn_parser::{self as parser, ast};
+use rotur_icn_resolver::{self as resolver, Compat, Resolver, lir};
+
+use crate::{Errors, LintConfig};
+
+/// A replacement of a range of the source
+#[derive(Debug, Clone)]
+pub struct Edit<'r> {
+    /// Byte range in the source before the edit
+    pub range: Range<usize>,
+    pub replacement: &'r str,
+}
+
+/// An ICN source, which is kept processed while it's edited
+///
+/// Only the commands an edit touches are lexed, parsed & lowered again,
+/// the following ones are just moved.
+/// Resolving restarts from the first changed operation.
+/// The results are the same as of [`process_with`](crate::process_with).
+#[derive(Debug, Clone)]
+pub struct Document {
+    src: String,
+    dialect: Dialect,
+    compat: Compat,
+    lints: LintConfig,
+    /// Whatever precedes the first command, followed by the commands
+    chunks: Vec<Chunk>,
+    hir: hir::IconHir,
+    lir: lir::IconLir,
+    resolving: Vec<resolver::Error>,
+    /// Resolver state before each operation & after the last one
+    checkpoints: Vec<Checkpoint>,
+}
+
+/// A command along with the trivia & errors up to the next one
+#[derive(Debug, Clone, Default)]
+struct Chunk {
+    /// None for the chunk before the first command
+    name_pos: Option<token::Pos>,
+    args: ArrayVec<ast::Argument, 6>,
+    trivia: Vec<(TriviaKind, token::Pos)>,
+    has_operation: bool,
+    lexing: Vec<lexer::Error>,
+    parsing: Vec<parser::Error>,
+    lowering: Vec<lowerer::Error>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TriviaKind {
+    Whitespace,
+    Comment,
+}
+
+#[derive(Debug, Clone)]
+struct Checkpoint {
+    resolver: Resolver,
+    elements: usize,
+    errors: usize,
+}
+
+/// Which side of the re-lexed chunks has to grow, to give the same result as lexing everything
+enum Grow {
+    Start,
+    End,
+}
+
+impl Document {
+    pub fn new(src: String, dialect: Dialect, compat: Compat, lints: LintConfig) -> Self {
+        let mut doc = Self {
+            src,
+            dialect,
+            compat,
+            lints,
+            chunks: vec![Chunk::default()],
+            hir: hir::IconHir {
+                operations: Vec::new(),
+            },
+            lir: lir::IconLir {
+                elements: Vec::new(),
+            },
+            resolving: Vec::new(),
+            checkpoints: vec![Checkpoint {
+                resolver: Resolver::new(compat),
+                elements: 0,
+                errors: 0,
+            }],
+        };
+
+        doc.reprocess(0, 1, 0)
+            .unwrap_or_else(|_| unreachable!("everything is re-lexed"));
+
+        doc
+    }
+
+    /// Apply the edit & reprocess the commands it touches
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of the source's bounds or not on char boundaries.
+    pub fn edit(&mut self, edit: &Edit) {
+        let Edit { range, replacement } = edit;
+
+        // the command before the edit is included, as arguments may be added to it
+        let mut first = self
+            .chunks
+            .partition_point(|chunk| chunk.start() < range.start)
+            .saturating_sub(1);
+        let mut end = self
+            .chunks
+            .partition_point(|chunk| chunk.start() < range.end)
+            .max(first + 1);
+
+        let delta = replacement.len().cast_signed() - range.len().cast_signed();
+
+        self.src.replace_range(range.clone(), replacement);
+
+        loop {
+            match self.reprocess(first, end, delta) {
+                Ok(()) => return,
+                Err(Grow::Start) => first -= 1,
+                Err(Grow::End) => end += 1,
+            }
+        }
+    }
+
+    pub fn src(&self) -> &str {
+        &self.src
+    }
+
+    pub fn ast(&self) -> ast::Icon<'_> {
+        let slice = |pos: &token::Pos| &self.src[pos.0.byte_idx..pos.1.byte_idx];
+        let trivia = |chunk: &Chunk| {
+            chunk
+                .trivia
+                .iter()
+                .map(|(kind, pos)| ast::Trivia {
+                    value: match kind {
+                        TriviaKind::Whitespace => token::Trivia::Whitespace(slice(pos)),
+                        TriviaKind::Comment => token::Trivia::Comment(slice(pos).trim_end()),
+                    },
+                    pos: *pos,
+                })
+                .collect()
+        };
+
+        ast::Icon {
+            trivia: trivia(&self.chunks[0]),
+            commands: self.chunks[1..]
+                .iter()
+                .map(|chunk| {
+                    #[expect(clippy::missing_panics_doc, reason = "for bug catching")]
+                    let name_pos = chunk.name_pos.expect("only the first chunk has no command");
+
+                    ast::Command {
+                        name: slice(&name_pos),
+                        name_pos,
+                        args: chunk.args.clone(),
+                        trivia: trivia(chunk),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    pub fn hir(&self) -> &hir::IconHir {
+        &self.hir
+    }
+
+    pub fn lir(&self) -> &lir::IconLir {
+        &self.lir
+    }
+
+    /// The errors of every chunk, with the whole ICN linted again
+    pub fn errors(&self) -> Errors {
+        Errors {
+            lexing: self
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.lexing.iter().cloned())
+                .collect(),
+            parsing: self
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.parsing.iter().cloned())
+                .collect(),
+            lowering: self
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.lowering.iter().cloned())
+                .collect(),
+            resolving: self.resolving.clone(),
+            linting: linter::lint(&self.hir, self.compat, &self.lints),
+        }
+    }
+
+    /// Replace the chunks in `first..end` by processing their source again
+    ///
+    /// The source after them has moved by `delta` bytes since the chunks were made.
+    fn reprocess(&mut self, first: usize, end: usize, delta: isize) -> Result<(), Grow> {
+        let (new_chunks, new_operations, end_loc) = self.relex(first, end, delta)?;
+
+        let old_commands = end - first.max(1);
+        let new_commands = new_chunks.len() - usize::from(first == 0);
+
+        let op_start = self.operations_before(first);
+        let op_end = self.operations_before(end);
+
+        if let Some((old_end_loc, new_end_loc)) = end_loc {
+            let shift = Shift {
+                old: old_end_loc,
+                new: new_end_loc,
+                commands: new_commands.cast_signed() - old_commands.cast_signed(),
+            };
+
+            for chunk in &mut self.chunks[end..] {
+                shift.chunk(chunk);
+            }
+
+            for op in &mut self.hir.operations[op_end..] {
+                shift.pos(&mut op.cmd_pos);
+            }
+        }
+
+        self.chunks.splice(first..end, new_chunks);
+        self.hir.operations.splice(op_start..op_end, new_operations);
+
+        self.resolve_from(op_start);
+
+        Ok(())
+    }
+
+    /// Lex, parse & lower the source of the chunks in `first..end` again
+    ///
+    /// Also returns the old & new location of the command following them.
+    #[expect(clippy::type_complexity, reason = "a private helper's many results")]
+    fn relex(
+        &self,
+        first: usize,
+        end: usize,
+        delta: isize,
+    ) -> Result<(Vec<Chunk>, Vec<hir::Operation>, Option<(Loc, Loc)>), Grow> {
+        let start_loc = self.chunks[first]
+            .name_pos
+            .map_or_else(Loc::default, |pos| pos.0);
+        let start = start_loc.byte_idx;
+
+        // the following command is still where it was, just moved
+        let boundary = self.chunks.get(end).map(|chunk| {
+            let old_loc = chunk
+                .name_pos
+                .expect("only the first chunk has no command")
+                .0;
+            (old_loc, old_loc.byte_idx.strict_add_signed(delta))
+        });
+
+        // lexing the first (ASCII) letter of the following command finds its new location,
+        // & checks that nothing before it runs into it
+        let lex_end = boundary.map_or(self.src.len(), |(_, byte_idx)| byte_idx + 1);
+
+        let mut lexing = Vec::new();
+        let mut tokens: Vec<token::PToken> =
+            lexer::lex_with(&mut lexing, &self.src[start..lex_end], self.dialect)
+                .map(|(l, token, r)| (absolute(start_loc, l), token, absolute(start_loc, r)))
+                .collect();
+
+        let end_loc = match boundary {
+            Some((old_loc, byte_idx)) => match tokens.pop() {
+                Some((l, token::Token::Identifier(_), _)) if l.byte_idx == byte_idx => {
+                    Some((old_loc, l))
+                }
+                _ => return Err(Grow::End),
+            },
+            None => None,
+        };
+
+        // the first command must still start where it did
+        if first != 0
+            && !matches!(
+                tokens.first(),
+                Some((l, token::Token::Identifier(_), _)) if l.byte_idx == start
+            )
+        {
+            return Err(Grow::Start);
+        }
+
+        for err in &mut lexing {
+            err.pos = (
+                absolute(start_loc, err.pos.0),
+                absolute(start_loc, err.pos.1),
+            );
+        }
+
+        let (icon, parsing) = parser::parse(tokens.into_iter());
+
+        let mut chunks = Vec::with_capacity(icon.commands.len() + 1);
+        let mut operations = Vec::with_capacity(icon.commands.len());
+
+        if first == 0 {
+            chunks.push(Chunk {
+                trivia: icon.trivia.iter().map(mirror_trivia).collect(),
+                ..Chunk::default()
+            });
+        }
+
+        let first_cmd_index = first.saturating_sub(1);
+
+        for (i, cmd) in icon.commands.into_iter().enumerate() {
+            let chunk = Chunk {
+                name_pos: Some(cmd.name_pos),
+                args: cmd.args.clone(),
+                trivia: cmd.trivia.iter().map(mirror_trivia).collect(),
+                ..Chunk::default()
+            };
+
+            // commands are lowered independently of each other
+            let (icon_high_ir, mut lowering) = lowerer::lower_with(
+                &ast::Icon {
+                    trivia: Vec::new(),
+                    commands: vec![cmd],
+                },
+                self.compat,
+            );
+
+            for err in &mut lowering {
+                err.cmd_index = first_cmd_index + i;
+            }
+
+            chunks.push(Chunk {
+                has_operation: !icon_high_ir.operations.is_empty(),
+                lowering,
+                ..chunk
+            });
+            operations.extend(icon_high_ir.operations);
+        }
+
+        // errors are kept along with the chunk they're in
+        let starts: Vec<usize> = chunks.iter().map(Chunk::start).collect();
+        let chunk_of = |loc: Loc| {
+            starts
+                .partition_point(|&start| start <= loc.byte_idx)
+                .saturating_sub(1)
+        };
+
+        for err in lexing {
+            let i = chunk_of(err.pos.0);
+            chunks[i].lexing.push(err);
+        }
+
+        for err in parsing {
+            let i = match &err {
+                parser::Error::TooManyArguments { keyword_pos, .. } => chunk_of(keyword_pos.0),
+                parser::Error::StrandedArguments { .. } => 0,
+            };
+            chunks[i].parsing.push(err);
+        }
+
+        Ok((chunks, operations, end_loc))
+    }
+
+    fn operations_before(&self, chunk_i: usize) -> usize {
+        self.chunks[..chunk_i]
+            .iter()
+            .filter(|chunk| chunk.has_operation)
+            .count()
+    }
+
+    /// Resolve the operations again, starting with the state before the operation
+    fn resolve_from(&mut self, op_i: usize) {
+        let checkpoint = self.checkpoints[op_i].clone();
+
+        self.checkpoints.truncate(op_i);
+        self.lir.elements.truncate(checkpoint.elements);
+        self.resolving.truncate(checkpoint.errors);
+
+        let mut resolver = checkpoint.resolver;
+
+        for (op_i, op) in self.hir.operations.iter().enumerate().skip(op_i) {
+            self.checkpoints.push(Checkpoint {
+                resolver: resolver.clone(),
+                elements: self.lir.elements.len(),
+                errors: self.resolving.len(),
+            });
+
+            if let Some(el) = resolver.step(&mut self.resolving, op_i, op) {
+                self.lir.elements.push(el);
+            }
+        }
+
+        self.checkpoints.push(Checkpoint {
+            resolver,
+            elements: self.lir.elements.len(),
+            errors: self.resolving.len(),
+        });
+    }
+}
+
+impl Chunk {
+    fn start(&self) -> usize {
+        self.name_pos.map_or(0, |pos| pos.0.byte_idx)
+    }
+}
+
+fn mirror_trivia(trivia: &ast::Trivia) -> (TriviaKind, token::Pos) {
+    let kind = match trivia.value {
+        token::Trivia::Whitespace(_) => TriviaKind::Whitespace,
+        token::Trivia::Comment(_) => TriviaKind::Comment,
+    };
+
+    (kind, trivia.pos)
+}
+
+/// Turn a location within a part of the source, starting at `start`, into one within the whole
+fn absolute(start: Loc, loc: Loc) -> Loc {
+    Loc {
+        line: start.line + loc.line,
+        col: if loc.line == 0 {
+            start.col + loc.col
+        } else {
+            loc.col
+        },
+        byte_idx: start.byte_idx + loc.byte_idx,
+    }
+}
+
+/// Movement of everything after an edit
+struct Shift {
+    old: Loc,
+    new: Loc,
+    /// Change in the number of commands
+    commands: isize,
+}
+
+impl Shift {
+    fn loc(&self, loc: &mut Loc) {
+        if loc.line == self.old.line {
+            loc.col = loc.col - self.old.col + self.new.col;
+        }
+
+        loc.line = loc.line - self.old.line + self.new.line;
+        loc.byte_idx = loc.byte_idx - self.old.byte_idx + self.new.byte_idx;
+    }
+
+    fn pos(&self, pos: &mut token::Pos) {
+        self.loc(&mut pos.0);
+        self.loc(&mut pos.1);
+    }
+
+    fn chunk(&self, chunk: &mut Chunk) {
+        if let Some(name_pos) = &mut chunk.name_pos {
+            self.pos(name_pos);
+        }
+
+        for arg in &mut chunk.args {
+            self.pos(&mut arg.pos);
+        }
+
+        for (_, pos) in &mut chunk.trivia {
+            self.pos(pos);
+        }
+
+        for err in &mut chunk.lexing {
+            self.pos(&mut err.pos);
+        }
+
+        for err in &mut chunk.parsing {
+            match err {
+                parser::Error::TooManyArguments {
+                    keyword_pos,
+                    overflow_pos,
+                } => {
+                    self.pos(keyword_pos);
+                    self.pos(overflow_pos);
+                }
+                parser::Error::StrandedArguments { stranded_pos } => self.pos(stranded_pos),
+            }
+        }
+
+        for err in &mut chunk.lowering {
+            self.lowering_error(err);
+        }
+    }
+
+    fn lowering_error(&self, err: &mut lowerer::Error) {
+        self.pos(&mut err.cmd_pos);
+        err.cmd_index = err.cmd_index.strict_add_signed(self.commands);
+
+        match &mut err.kind {
+            lowerer::ErrorKind::TooManyArguments { overflow_pos, .. } => self.pos(overflow_pos),
+            lowerer::ErrorKind::TooFewArguments { args_end_loc, .. } => self.loc(args_end_loc),
+            lowerer::ErrorKind::UnexpectedLiteralKind { arg_pos, .. }
+            | lowerer::ErrorKind::InvalidNumericColour { arg_pos, .. }
+            | lowerer::ErrorKind::ArgOutOfRange { arg_pos, .. } => self.pos(arg_pos),
+            lowerer::ErrorKind::InvalidCommand { .. } => {}
+        }
+    }
+}
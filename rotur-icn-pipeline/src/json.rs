@@ -0,0 +1,343 @@
+use std::{
+    borrow::Cow,
+    fmt::Write as _,
+    ops::Range,
+    path::{Component, Path},
+};
+
+use codespan_reporting::diagnostic::{Diagnostic, LabelStyle, Severity};
+use serde_json::{Value, json};
+
+use crate::{
+    Errors, Suggestion,
+    diagnostics::{
+        LexerErrorDiagnostic, LinterWarningDiagnostic, LowererErrorDiagnostic,
+        ParserErrorDiagnostic, ResolverErrorDiagnostic,
+    },
+};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+/// The base, which relative paths of sources are resolved against
+const SARIF_SRCROOT: &str = "%SRCROOT%";
+
+impl Errors {
+    /// Serialise the errors into a JSON array, in the order of the stages
+    ///
+    /// Each entry holds its `stage`, `severity`, `code`, `message`, `help` (or `null`),
+    /// the `range` of its primary label, all of its `labels`
+    /// & a `suggestion` (or `null`) of a `replacement` for a `range`.
+    /// A range holds the `byte` offsets of its `start` & `end`,
+    /// together with their 1-based `line` & `column` (in unicode chars).
+    pub fn to_json(&self, src: &str) -> Value {
+        let index = LineIndex::new(src);
+
+        self.entries()
+            .map(|entry| {
+                let labels = entry
+                    .diagnostic
+                    .labels
+                    .iter()
+                    .map(|label| {
+                        json!({
+                            "primary": label.style == LabelStyle::Primary,
+                            "message": label.message,
+                            "range": index.range(label.range.clone()),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                json!({
+                    "stage": entry.stage,
+                    "severity": severity_name(entry.diagnostic.severity),
+                    "code": entry.code,
+                    "message": entry.diagnostic.message,
+                    "help": entry.help,
+                    "range": index.range(entry.primary_range()),
+                    "labels": labels,
+                    "suggestion": entry.suggestion.map(|suggestion| json!({
+                        "range": index.range(suggestion.range()),
+                        "replacement": suggestion.replacement,
+                    })),
+                })
+            })
+            .collect()
+    }
+
+    /// Serialise the errors into a SARIF 2.1.0 log with a single run
+    ///
+    /// `path` locates the source in the results; it's left out, when there's none (e.g. stdin).
+    /// An absolute path is given as a `file://` URI,
+    /// a relative one as a relative URI against `%SRCROOT%`.
+    /// Suggestions are included as fixes, which need the `path`.
+    pub fn to_sarif(&self, src: &str, path: Option<&Path>) -> Value {
+        let index = LineIndex::new(src);
+        let artifact = path.map(artifact_location);
+
+        let mut rules = Vec::<(&str, &str)>::new();
+        let mut results = Vec::new();
+
+        for entry in self.entries() {
+            let code = entry.code;
+
+            let rule_index = rules
+                .iter()
+                .position(|(rule, _)| *rule == code)
+                .unwrap_or_else(|| {
+                    rules.push((code, entry.stage));
+                    rules.len() - 1
+                });
+
+            let location = |range: Range<usize>| {
+                let mut physical = json!({ "region": sarif_region(&index, range) });
+
+                if let Some(artifact) = &artifact {
+                    physical["artifactLocation"] = artifact.clone();
+                }
+
+                physical
+            };
+
+            let related = entry
+                .diagnostic
+                .labels
+                .iter()
+                .filter(|label| label.style == LabelStyle::Secondary)
+                .map(|label| {
+                    json!({
+                        "physicalLocation": location(label.range.clone()),
+                        "message": { "text": label.message },
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let mut text = entry.diagnostic.message.clone();
+            if let Some(help) = &entry.help {
+                text.push_str("\nhelp: ");
+                text.push_str(help);
+            }
+
+            let fixes = artifact
+                .as_ref()
+                .zip(entry.suggestion.as_ref())
+                .map(|(artifact, suggestion)| {
+                    json!({
+                        "description": { "text": suggestion.to_string() },
+                        "artifactChanges": [{
+                            "artifactLocation": artifact,
+                            "replacements": [{
+                                "deletedRegion": sarif_region(&index, suggestion.range()),
+                                "insertedContent": { "text": suggestion.replacement },
+                            }],
+                        }],
+                    })
+                })
+                .into_iter()
+                .collect::<Vec<_>>();
+
+            results.push(json!({
+                "ruleId": code,
+                "ruleIndex": rule_index,
+                "level": sarif_level(entry.diagnostic.severity),
+                "message": { "text": text },
+                "locations": [{ "physicalLocation": location(entry.primary_range()) }],
+                "relatedLocations": related,
+                "fixes": fixes,
+                "properties": { "stage": entry.stage },
+            }));
+        }
+
+        let rules = rules
+            .into_iter()
+            .map(|(code, stage)| json!({ "id": code, "properties": { "stage": stage } }))
+            .collect::<Vec<_>>();
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "rotur-icn",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    }
+                },
+                "columnKind": "unicodeCodePoints",
+                "results": results,
+            }]
+        })
+    }
+
+    fn entries(&self) -> impl Iterator<Item = Entry> {
+        let lexing = self.lexing.iter().map(|err| Entry {
+            stage: "lexing",
+            code: err.kind.code(),
+            help: Some(Cow::Borrowed(err.kind.help())),
+            suggestion: err.suggestion(),
+            diagnostic: LexerErrorDiagnostic(err).into(),
+        });
+        let parsing = self.parsing.iter().map(|err| Entry {
+            stage: "parsing",
+            code: err.code(),
+            help: Some(Cow::Borrowed(err.help())),
+            suggestion: err.suggestion(),
+            diagnostic: ParserErrorDiagnostic(err).into(),
+        });
+        let lowering = self.lowering.iter().map(|err| Entry {
+            stage: "lowering",
+            code: err.kind.code(),
+            help: err.kind.help(),
+            suggestion: err.suggestion(),
+            diagnostic: LowererErrorDiagnostic(err).into(),
+        });
+        let resolving = self.resolving.iter().map(|err| Entry {
+            stage: "resolving",
+            code: err.kind.code(),
+            help: err.kind.help().map(Cow::Borrowed),
+            suggestion: err.suggestion(),
+            diagnostic: ResolverErrorDiagnostic(err).into(),
+        });
+
+        let linting = self.linting.iter().map(|warning| Entry {
+            stage: "linting",
+            code: warning.kind.code(),
+            help: warning.kind.help().map(Cow::Borrowed),
+            suggestion: None,
+            diagnostic: LinterWarningDiagnostic(warning).into(),
+        });
+
+        lexing
+            .chain(parsing)
+            .chain(lowering)
+            .chain(resolving)
+            .chain(linting)
+    }
+}
+
+struct Entry {
+    stage: &'static str,
+    code: &'static str,
+    help: Option<Cow<'static, str>>,
+    suggestion: Option<Suggestion>,
+    diagnostic: Diagnostic<()>,
+}
+
+impl Entry {
+    fn primary_range(&self) -> Range<usize> {
+        self.diagnostic
+            .labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+            .map_or(0..0, |label| label.range.clone())
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "note",
+    }
+}
+
+/// The SARIF artifact location of a source's path
+fn artifact_location(path: &Path) -> Value {
+    let mut uri = String::new();
+
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => {
+                uri.push_str(&encode_uri_segment(&prefix.as_os_str().to_string_lossy()));
+            }
+            Component::RootDir | Component::CurDir => continue,
+            Component::ParentDir => uri.push_str(".."),
+            Component::Normal(segment) => {
+                uri.push_str(&encode_uri_segment(&segment.to_string_lossy()));
+            }
+        }
+
+        uri.push('/');
+    }
+
+    // the last segment is a file, not a directory
+    uri.pop();
+
+    if path.is_absolute() {
+        json!({ "uri": format!("file:///{uri}") })
+    } else {
+        json!({ "uri": uri, "uriBaseId": SARIF_SRCROOT })
+    }
+}
+
+/// Percent-encode everything but the unreserved chars & the ones allowed in a path segment
+fn encode_uri_segment(segment: &str) -> String {
+    let mut encoded = String::new();
+
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            write!(encoded, "%{byte:02X}").expect("writing to a string doesn't fail");
+        }
+    }
+
+    encoded
+}
+
+fn sarif_region(index: &LineIndex, range: Range<usize>) -> Value {
+    let (start_line, start_column) = index.line_column(range.start);
+    let (end_line, end_column) = index.line_column(range.end);
+
+    json!({
+        "byteOffset": range.start,
+        "byteLength": range.len(),
+        "startLine": start_line,
+        "startColumn": start_column,
+        "endLine": end_line,
+        "endColumn": end_column,
+    })
+}
+
+/// Converts byte offsets into 1-based lines & columns
+struct LineIndex<'s> {
+    src: &'s str,
+    line_starts: Vec<usize>,
+}
+
+impl<'s> LineIndex<'s> {
+    fn new(src: &'s str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(src.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self { src, line_starts }
+    }
+
+    fn line_column(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.src.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+
+        (line + 1, self.src[line_start..offset].chars().count() + 1)
+    }
+
+    fn range(&self, range: Range<usize>) -> Value {
+        let point = |byte| {
+            let (line, column) = self.line_column(byte);
+            json!({ "byte": byte, "line": line, "column": column })
+        };
+
+        json!({ "start": point(range.start), "end": point(range.end) })
+    }
+}
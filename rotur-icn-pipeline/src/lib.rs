@@ -1,18 +1,44 @@
 use rotur_icn_lexer as lexer;
+pub use rotur_icn_lexer::{Dialect, Suggestion};
+use rotur_icn_linter as linter;
+pub use rotur_icn_linter::{Config as LintConfig, Level, Lint};
 use rotur_icn_lowerer::{self as lowerer, hir};
 use rotur_icn_parser::{self as parser, ast};
+pub use rotur_icn_resolver::Compat;
 use rotur_icn_resolver::{self as resolver, lir};
 
 #[cfg(feature = "diagnostics")]
 mod diagnostics;
+mod fix;
+mod incremental;
+#[cfg(feature = "json")]
+mod json;
+
+pub use fix::apply_suggestions;
+pub use incremental::{Document, Edit};
 
 pub fn process(src: &'_ str) -> (ast::Icon<'_>, hir::IconHir, lir::IconLir, Errors) {
+    process_with(
+        src,
+        Dialect::default(),
+        Compat::default(),
+        &LintConfig::default(),
+    )
+}
+
+pub fn process_with<'s>(
+    src: &'s str,
+    dialect: Dialect,
+    compat: Compat,
+    lints: &LintConfig,
+) -> (ast::Icon<'s>, hir::IconHir, lir::IconLir, Errors) {
     let mut lexing_errors = Vec::new();
 
-    let lexer = lexer::lex(&mut lexing_errors, src);
+    let lexer = lexer::lex_with(&mut lexing_errors, src, dialect);
     let (icon_ast, parsing_errors) = parser::parse(lexer);
-    let (icon_high_ir, lowering_errors) = lowerer::lower(&icon_ast);
-    let (icon_low_ir, resolving_errors) = resolver::resolve(&icon_high_ir);
+    let (icon_high_ir, lowering_errors) = lowerer::lower_with(&icon_ast, compat);
+    let (icon_low_ir, resolving_errors) = resolver::resolve_with(&icon_high_ir, compat);
+    let linting_warnings = linter::lint(&icon_high_ir, compat, lints);
 
     (
         icon_ast,
@@ -23,6 +49,7 @@ pub fn process(src: &'_ str) -> (ast::Icon<'_>, hir::IconHir, lir::IconLir, Erro
             parsing: parsing_errors,
             lowering: lowering_errors,
             resolving: resolving_errors,
+            linting: linting_warnings,
         },
     )
 }
@@ -32,17 +59,31 @@ pub struct Errors {
     pub parsing: Vec<parser::Error>,
     pub lowering: Vec<lowerer::Error>,
     pub resolving: Vec<resolver::Error>,
+    /// Lints found at their configured levels, without the allowed ones
+    pub linting: Vec<linter::Warning>,
 }
 
 impl Errors {
+    /// Whether there are no errors, counting denied lints, but not warnings
     pub fn is_empty(&self) -> bool {
         self.lexing.is_empty()
             && self.parsing.is_empty()
             && self.lowering.is_empty()
             && self.resolving.is_empty()
+            && !self.linting.iter().any(linter::Warning::is_denied)
     }
 
+    /// Number of the errors, counting denied lints, but not warnings
     pub fn len(&self) -> usize {
-        self.lexing.len() + self.parsing.len() + self.lowering.len() + self.resolving.len()
+        self.lexing.len()
+            + self.parsing.len()
+            + self.lowering.len()
+            + self.resolving.len()
+            + self.linting.iter().filter(|w| w.is_denied()).count()
+    }
+
+    /// Number of the lints, which are only warned about
+    pub fn warnings_len(&self) -> usize {
+        self.linting.iter().filter(|w| !w.is_denied()).count()
     }
 }
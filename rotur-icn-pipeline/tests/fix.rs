@@ -0,0 +1,75 @@
+use rotur_icn_lowerer::ErrorKind;
+use rotur_icn_pipeline::apply_suggestions;
+
+fn fix(src: &str) -> String {
+    let (_, _, _, errors) = rotur_icn_pipeline::process(src);
+    let (fixed, _) = apply_suggestions(src, &errors.suggestions());
+
+    fixed
+}
+
+#[test]
+fn dangling_continued_lines_are_left_alone() {
+    let src = "w 2 cont 3 4 cont 5 6";
+    let (_, _, _, errors) = rotur_icn_pipeline::process(src);
+
+    assert_eq!(errors.resolving.len(), 1);
+    assert_eq!(fix(src), src);
+}
+
+#[test]
+fn swapped_chars_are_a_single_typo() {
+    assert_eq!(fix("lien 0 0 1 1"), "line 0 0 1 1");
+}
+
+#[test]
+fn aliases_taking_the_same_arguments_are_renamed() {
+    let cases = [
+        ("width 2", "w 2"),
+        ("linewidth 2", "w 2"),
+        ("colour #f00", "c #f00"),
+        ("color #f00", "c #f00"),
+        ("fill #f00", "c #f00"),
+        ("stroke #f00", "c #f00"),
+        ("line 0 0 1 1 lineto 2 2", "line 0 0 1 1 cont 2 2"),
+        ("point 1 2", "dot 1 2"),
+        ("triangle 0 0 1 1 2 0", "tri 0 0 1 1 2 0"),
+        ("translate 1 2", "move 1 2"),
+        ("move 1 2 origin", "move 1 2 back"),
+        ("move 1 2 reset", "move 1 2 back"),
+    ];
+
+    for (src, expected) in cases {
+        let fixed = fix(src);
+        assert_eq!(fixed, expected);
+
+        let (_, _, _, errors) = rotur_icn_pipeline::process(&fixed);
+        assert!(errors.is_empty(), "{fixed:?} has errors");
+    }
+}
+
+#[test]
+fn aliases_taking_different_arguments_are_left_alone() {
+    for src in [
+        "circle 0 0 5",
+        "polygon 0 0 1 1 2 0",
+        "rectangle 0 0 4 2",
+        "fillrect 0 0 4 2",
+        "box 0 0 4 2",
+        "strokerect 0 0 4 2",
+        "moveto 1 2",
+    ] {
+        assert_eq!(fix(src), src);
+    }
+}
+
+#[test]
+fn equally_close_commands_are_left_alone() {
+    let (_, _, _, errors) = rotur_icn_pipeline::process("cot 1 1");
+
+    assert!(matches!(
+        &errors.lowering[0].kind,
+        ErrorKind::InvalidCommand { similar, .. } if similar[..2] == ["cont", "dot"]
+    ));
+    assert_eq!(fix("cot 1 1"), "cot 1 1");
+}
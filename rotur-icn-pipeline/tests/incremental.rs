@@ -0,0 +1,107 @@
+use rand::{
+    Rng as _, SeedableRng as _, distr::Distribution as _, rngs::StdRng, seq::IndexedRandom as _,
+};
+
+use rotur_icn_lowerer::hir;
+use rotur_icn_pipeline::{Compat, Dialect, Document, Edit, Errors, LintConfig, process_with};
+use rotur_icn_rand::IcnSampler;
+
+/// Pieces of source, which the random edits insert
+const SNIPPETS: &[&str] = &[
+    "",
+    " ",
+    "\n",
+    "\t",
+    "1",
+    "-2.5",
+    "e3",
+    ".",
+    "#",
+    "#fff",
+    "#12ab",
+    "w",
+    "line ",
+    "cont 3 4 ",
+    "dot 1 1\n",
+    "c #f00 ",
+    "back ",
+    "// note",
+    "//",
+    "é",
+    "x 1",
+];
+
+fn check_edits(dialect: Dialect) {
+    let sampler = IcnSampler::default();
+    let mut rng = StdRng::seed_from_u64(0x1c4);
+
+    for _ in 0..12 {
+        let icon: hir::IconHir = sampler.sample(&mut rng);
+        let src = rotur_icn_printer::print_hir(&icon, rng.random());
+
+        let mut doc = Document::new(src, dialect, Compat::default(), LintConfig::default());
+
+        for _ in 0..50 {
+            let src = doc.src();
+
+            let a = random_boundary(&mut rng, src);
+            let b = random_boundary(&mut rng, src).max(a).min(a + 8);
+            let b = (b..=src.len())
+                .find(|&b| src.is_char_boundary(b))
+                .expect("the end is a boundary");
+
+            doc.edit(&Edit {
+                range: a..b,
+                replacement: SNIPPETS.choose(&mut rng).expect("there are snippets"),
+            });
+
+            assert_eq!(
+                summarise(&doc),
+                summarise_whole(doc.src(), dialect),
+                "after editing {a}..{b} into {:?}",
+                doc.src()
+            );
+        }
+    }
+}
+
+/// Editing random ICNs randomly keeps the document as if it was processed whole
+#[test]
+fn edits_match_processing_whole() {
+    check_edits(Dialect { comments: false });
+}
+
+#[test]
+fn edits_match_processing_whole_with_comments() {
+    check_edits(Dialect { comments: true });
+}
+
+fn random_boundary(rng: &mut impl rand::Rng, src: &str) -> usize {
+    let i = rng.random_range(0..=src.len());
+    (0..=i)
+        .rev()
+        .find(|&i| src.is_char_boundary(i))
+        .expect("the start is a boundary")
+}
+
+fn summarise(doc: &Document) -> String {
+    summary(&doc.ast(), doc.hir(), doc.lir(), &doc.errors())
+}
+
+fn summarise_whole(src: &str, dialect: Dialect) -> String {
+    let (ast, hir, lir, errors) =
+        process_with(src, dialect, Compat::default(), &LintConfig::default());
+    summary(&ast, &hir, &lir, &errors)
+}
+
+fn summary(
+    ast: &rotur_icn_parser::ast::Icon,
+    hir: &hir::IconHir,
+    lir: &rotur_icn_resolver::lir::IconLir,
+    errors: &Errors,
+) -> String {
+    format!(
+        "{ast:?}\n{hir:?}\n{lir:?}\n{:?}\n{:?}\n{:?}\n{:?}\n{:?}",
+        errors.lexing, errors.parsing, errors.lowering, errors.resolving, errors.linting
+    )
+}
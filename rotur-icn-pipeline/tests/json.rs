@@ -0,0 +1,93 @@
+#![cfg(feature = "json")]
+
+use serde_json::{Value, json};
+
+fn to_json(src: &str) -> Vec<Value> {
+    let (_, _, _, errors) = rotur_icn_pipeline::process(src);
+
+    match errors.to_json(src) {
+        Value::Array(entries) => entries,
+        json => panic!("{json} isn't an array"),
+    }
+}
+
+#[test]
+fn entries_hold_their_stage_code_help_range_and_suggestion() {
+    let range = json!({
+        "start": { "byte": 10, "line": 2, "column": 3 },
+        "end": { "byte": 14, "line": 2, "column": 7 },
+    });
+
+    assert_eq!(
+        to_json("dot 0 0\n  lien 1 2 3 4"),
+        [json!({
+            "stage": "lowering",
+            "severity": "error",
+            "code": "EW05",
+            "message": "unknown command",
+            "help": "did you mean `line`?",
+            "range": range,
+            "labels": [{
+                "primary": true,
+                "message": "did you mean `line`?",
+                "range": range,
+            }],
+            "suggestion": {
+                "range": range,
+                "replacement": "line",
+            },
+        })]
+    );
+}
+
+#[test]
+fn entries_are_in_the_order_of_the_stages() {
+    let entries = to_json("dot 0 0\nc #ggg\nlien 1 2 3 4");
+
+    let stages = entries
+        .iter()
+        .map(|entry| {
+            (
+                entry["stage"].as_str().unwrap_or_default(),
+                entry["severity"].as_str().unwrap_or_default(),
+                entry["suggestion"].is_null(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        stages,
+        [
+            ("lexing", "error", true),
+            ("lowering", "error", false),
+            ("linting", "warning", true),
+        ]
+    );
+}
+
+#[test]
+fn columns_count_chars_and_bytes_count_bytes() {
+    let entries = to_json("é lien 1 2 3 4");
+
+    let starts = entries
+        .iter()
+        .map(|entry| entry["range"]["start"].clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        starts,
+        [
+            json!({ "byte": 0, "line": 1, "column": 1 }),
+            json!({ "byte": 3, "line": 1, "column": 3 }),
+        ]
+    );
+}
+
+#[test]
+fn clean_sources_give_empty_reports() {
+    let src = "c #f00 dot 0 0";
+    let (_, _, _, errors) = rotur_icn_pipeline::process(src);
+
+    assert_eq!(errors.to_json(src), json!([]));
+    assert_eq!(errors.to_sarif(src, None)["runs"][0]["results"], json!([]));
+}
@@ -0,0 +1,35 @@
+use rotur_icn_pipeline::{Compat, Dialect, Lint, LintConfig, process_with};
+
+const SRC: &str = "w 0 c #f00 dot 20 0";
+
+fn errors_with(lints: &LintConfig) -> (bool, usize, usize) {
+    let (_, _, _, errors) = process_with(SRC, Dialect::default(), Compat::default(), lints);
+
+    (errors.is_empty(), errors.len(), errors.warnings_len())
+}
+
+#[test]
+fn warned_lints_arent_errors() {
+    assert_eq!(errors_with(&LintConfig::default()), (true, 0, 2));
+}
+
+#[test]
+fn denied_lints_are_errors() {
+    let mut lints = LintConfig::default();
+    lints.deny(Lint::OffCanvas);
+
+    assert_eq!(errors_with(&lints), (false, 1, 1));
+
+    lints.deny(Lint::ZeroWidth);
+
+    assert_eq!(errors_with(&lints), (false, 2, 0));
+}
+
+#[test]
+fn allowed_lints_arent_counted() {
+    let mut lints = LintConfig::default();
+    lints.allow(Lint::OffCanvas);
+    lints.allow(Lint::ZeroWidth);
+
+    assert_eq!(errors_with(&lints), (true, 0, 0));
+}
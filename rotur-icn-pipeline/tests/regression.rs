@@ -0,0 +1,119 @@
+use std::{fs, io::Write as _, path::Path};
+
+use rotur_icn_pipeline::{Compat, Dialect, LintConfig, process_with};
+use rotur_icn_renderer::cpu::Renderer;
+use rotur_icn_resolver::lir;
+use rotur_icn_units::{Colour, Number, Vector};
+
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/regression");
+
+/// The canvas originOS draws ICNs on
+const CANVAS: Vector = Vector { x: 20., y: 20. };
+const SCALE: Number = 3.;
+
+/// Check the ICNs of the corpus resolve & render to their expected LIR & raster,
+/// which reads back into the resolved LIR
+///
+/// With `BLESS` set, the expectations are written from the current results instead.
+#[test]
+fn corpus_resolves_and_renders_as_expected() {
+    let bless = std::env::var_os("BLESS").is_some();
+
+    let mut paths = fs::read_dir(CORPUS)
+        .expect("the corpus should be readable")
+        .map(|entry| entry.expect("the corpus should be readable").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "icn"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    assert!(!paths.is_empty(), "the corpus should have cases");
+
+    let mut mismatches = Vec::new();
+
+    for path in &paths {
+        let name = path
+            .file_stem()
+            .expect("the case has a name")
+            .to_string_lossy();
+
+        let src = fs::read_to_string(path).expect("the case should be readable");
+        let (_, _, icon, _) = process_with(
+            &src,
+            Dialect::default(),
+            Compat::default(),
+            &LintConfig::default(),
+        );
+
+        let lir_text = icon.to_string();
+        let raster = render_pam(&icon);
+
+        let lir_path = path.with_extension("lir");
+        let pam_path = path.with_extension("pam");
+
+        if bless {
+            fs::write(&lir_path, &lir_text).expect("the LIR should be writable");
+            fs::write(&pam_path, &raster).expect("the raster should be writable");
+            continue;
+        }
+
+        if !is_expected(&lir_path, lir_text.as_bytes()) {
+            mismatches.push(format!("{name}: LIR differs"));
+        }
+
+        if !is_expected(&pam_path, &raster) {
+            mismatches.push(format!("{name}: raster differs"));
+        }
+
+        if !reads_back(&lir_path, &icon) {
+            mismatches.push(format!(
+                "{name}: LIR doesn't read back into the resolved one"
+            ));
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "{} of {} cases mismatch, run with `BLESS=1` to accept the changes\n{}",
+        mismatches.len(),
+        paths.len(),
+        mismatches.join("\n"),
+    );
+}
+
+/// Whether the expected LIR reads back into exactly the resolved one
+fn reads_back(path: &Path, resolved: &lir::IconLir) -> bool {
+    fs::read_to_string(path).is_ok_and(|expected| {
+        expected
+            .parse::<lir::IconLir>()
+            .is_ok_and(|icon| icon == *resolved)
+    })
+}
+
+fn is_expected(path: &Path, actual: &[u8]) -> bool {
+    fs::read(path).is_ok_and(|expected| expected == actual)
+}
+
+/// Render the ICN without anti-aliasing onto a transparent canvas, as the viewer saves a PAM
+fn render_pam(icon: &lir::IconLir) -> Vec<u8> {
+    let mut renderer = Renderer::new(CANVAS, SCALE, Vector::ZERO, Colour::ZERO);
+    renderer.load(icon);
+
+    let (mut buf, (width, height)) = renderer.new_buf();
+    renderer.render(&mut buf);
+
+    let mut pam = Vec::new();
+    write!(
+        pam,
+        "P7\n\
+        WIDTH {width}\n\
+        HEIGHT {height}\n\
+        DEPTH 4\n\
+        MAXVAL 255\n\
+        TUPLTYPE RGB_ALPHA\n\
+        ENDHDR\n",
+    )
+    .expect("writing to a vec doesn't fail");
+    pam.extend_from_slice(&buf);
+
+    pam
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "json")]
+
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+fn artifact_location(path: &str) -> Value {
+    let (_, _, _, errors) = rotur_icn_pipeline::process("x 1");
+    let sarif = errors.to_sarif("x 1", Some(Path::new(path)));
+
+    sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"].clone()
+}
+
+#[test]
+fn relative_paths_are_relative_to_srcroot() {
+    assert_eq!(
+        artifact_location("./icons/../a b.icn"),
+        json!({ "uri": "icons/../a%20b.icn", "uriBaseId": "%SRCROOT%" })
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn absolute_paths_are_file_uris() {
+    assert_eq!(
+        artifact_location("/home/me/icöns/a#1.icn"),
+        json!({ "uri": "file:///home/me/ic%C3%B6ns/a%231.icn" })
+    );
+}
@@ -2,8 +2,13 @@ use rotur_icn_lexer::token;
 use rotur_icn_lowerer::hir;
 use rotur_icn_parser::ast;
 
+#[expect(
+    clippy::too_many_lines,
+    reason = "a flat match over every operation kind"
+)]
 pub fn convert(hir: &hir::IconHir) -> ast::Icon<'static> {
     ast::Icon {
+        trivia: Vec::new(),
         commands: hir
             .operations
             .iter()
@@ -129,6 +134,7 @@ fn make_command<'s>(
                 pos: op.cmd_pos,
             })
             .collect(),
+        trivia: Vec::new(),
     }
 }
 
@@ -6,11 +6,11 @@ mod stringifier;
 mod transformer;
 
 pub use converter::convert;
-pub use stringifier::stringify;
-pub use transformer::transform;
+pub use stringifier::{Layout, Style, stringify, stringify_styled};
+pub use transformer::{transform, transform_with};
 
 pub fn print_lir(icon: &lir::IconLir, oneline: bool) -> String {
-    print_hir(&transform(icon), oneline)
+    print_hir(&transform(icon, false), oneline)
 }
 
 pub fn print_hir(icon: &hir::IconHir, oneline: bool) -> String {
@@ -3,32 +3,171 @@
 use std::fmt::Write as _;
 
 use rotur_icn_lexer::token;
+use rotur_icn_lowerer::hir;
 use rotur_icn_parser::ast;
+use rotur_icn_units::{Colour, Number};
+
+/// How commands are laid out in the source
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// All commands on a single line
+    Oneline,
+    /// Each command on its own line
+    #[default]
+    PerLine,
+    /// A line per block of commands, starting at each colour or width change
+    Grouped,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Style {
+    pub layout: Layout,
+    /// Number of decimal places to round numbers to, trailing zeros are trimmed
+    pub precision: Option<usize>,
+    /// Write colours as `#rgb`, when it's exact
+    pub short_colours: bool,
+}
 
 pub fn stringify(ast: &ast::Icon, oneline: bool) -> String {
+    stringify_styled(
+        ast,
+        &Style {
+            layout: if oneline {
+                Layout::Oneline
+            } else {
+                Layout::PerLine
+            },
+            ..Style::default()
+        },
+    )
+}
+
+pub fn stringify_styled(ast: &ast::Icon, style: &Style) -> String {
     // TODO with capacity? maybe based on some estimates of bytes/command
     let mut buf = String::new();
 
-    for cmd in &ast.commands {
+    let keep_blank_lines = style.layout != Layout::Oneline;
+
+    // comments before the first command each get their own line
+    let (commented, blank_line) = write_comments(&mut buf, &ast.trivia, keep_blank_lines);
+    if commented {
+        writeln!(buf).unwrap();
+
+        if blank_line {
+            writeln!(buf).unwrap();
+        }
+    }
+
+    for (i, cmd) in ast.commands.iter().enumerate() {
         write!(buf, "{}", &cmd.name).unwrap();
 
         for arg in &cmd.args {
+            buf.push(' ');
+
             match &arg.lit {
-                token::Literal::Number(n) => write!(buf, " {n}").unwrap(),
-                // TODO handle those which are representable as #fff
+                token::Literal::Number(n) => write_number(&mut buf, *n, style.precision),
                 // TODO handle alpha somehow, maybe panic?
-                token::Literal::Colour(col) => {
-                    write!(buf, " #{:0>2x}{:0>2x}{:0>2x}", col.r, col.g, col.b).unwrap();
-                }
+                token::Literal::Colour(col) => write_colour(&mut buf, *col, style.short_colours),
             }
         }
 
-        if oneline {
-            write!(buf, " ").unwrap();
-        } else {
+        let (commented, blank_line) = write_comments(&mut buf, &cmd.trivia, keep_blank_lines);
+
+        // a comment runs until the end of the line
+        let newline = commented
+            || match style.layout {
+                Layout::Oneline => false,
+                Layout::PerLine => true,
+                // state changes in a row stay in a single block
+                Layout::Grouped => ast
+                    .commands
+                    .get(i + 1)
+                    .is_none_or(|next| sets_state(next) && !sets_state(cmd)),
+            };
+
+        if newline {
             writeln!(buf).unwrap();
+
+            if blank_line && i + 1 < ast.commands.len() {
+                writeln!(buf).unwrap();
+            }
+        } else {
+            write!(buf, " ").unwrap();
         }
     }
 
     buf
 }
+
+/// Write the comments in the trivia, keeping them on the same line, if they were
+///
+/// Returns whether any comment was written & whether the trivia ends with a blank line.
+fn write_comments(
+    buf: &mut String,
+    trivia: &[ast::Trivia],
+    keep_blank_lines: bool,
+) -> (bool, bool) {
+    let mut commented = false;
+    let mut newlines = 0;
+
+    for trivia in trivia {
+        match trivia.value {
+            // whitespace is lexed in whole runs, so only the latest one counts
+            token::Trivia::Whitespace(ws) => newlines = ws.matches('\n').count(),
+            token::Trivia::Comment(comment) => {
+                if buf.is_empty() || buf.ends_with('\n') {
+                    // nothing to share a line with
+                } else if newlines == 0 {
+                    buf.push(' ');
+                } else {
+                    buf.push('\n');
+                }
+
+                if newlines > 1 && keep_blank_lines && !buf.is_empty() {
+                    buf.push('\n');
+                }
+
+                buf.push_str(comment);
+
+                commented = true;
+                newlines = 0;
+            }
+        }
+    }
+
+    (commented, newlines > 1 && keep_blank_lines)
+}
+
+fn sets_state(cmd: &ast::Command) -> bool {
+    cmd.name == hir::SetColour::NAME || cmd.name == hir::SetWidth::NAME
+}
+
+fn write_number(buf: &mut String, n: Number, precision: Option<usize>) {
+    let Some(precision) = precision else {
+        write!(buf, "{n}").unwrap();
+        return;
+    };
+
+    let start = buf.len();
+    write!(buf, "{n:.precision$}").unwrap();
+
+    if buf[start..].contains('.') {
+        let trimmed = buf.trim_end_matches('0').trim_end_matches('.').len();
+        buf.truncate(trimmed);
+    }
+
+    // rounding may leave a negative zero behind
+    if &buf[start..] == "-0" {
+        buf.replace_range(start.., "0");
+    }
+}
+
+fn write_colour(buf: &mut String, col: Colour, short: bool) {
+    let shortenable = [col.r, col.g, col.b].iter().all(|c| c % 17 == 0);
+
+    if short && shortenable {
+        write!(buf, "#{:x}{:x}{:x}", col.r / 17, col.g / 17, col.b / 17).unwrap();
+    } else {
+        write!(buf, "#{:0>2x}{:0>2x}{:0>2x}", col.r, col.g, col.b).unwrap();
+    }
+}
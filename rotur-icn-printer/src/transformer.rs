@@ -1,151 +1,275 @@
-use arrayvec::ArrayVec;
-
 use rotur_icn_lexer::token;
 use rotur_icn_lowerer::hir;
-use rotur_icn_resolver::lir;
-use rotur_icn_units::Colour;
+use rotur_icn_resolver::{Compat, Resolver, lir};
+use rotur_icn_units::{Number, Vector};
+
+use crate::{convert, stringify};
+
+/// How many upcoming elements are weighed, when deciding whether to move the centre
+const ORIGIN_LOOKAHEAD: usize = 16;
+
+// XXX address this somehow?
+const LOC: token::Loc = token::Loc {
+    line: 0,
+    col: 0,
+    byte_idx: 0,
+};
+const CMD_POS: token::Pos = (LOC, LOC);
+
+/// Turn the LIR back into operations, which resolve to the same elements
+///
+/// Lines starting at the last point are continued.
+/// With `factor_origins`, the centre is moved next to runs of elements,
+/// when that makes their coordinates shorter.
+///
+/// Every emitted operation is checked against the resolver,
+/// so the result is identical, unless an element can't be expressed exactly
+/// (e.g. arcs & ellipses, whose angles get converted).
+pub fn transform(lir: &lir::IconLir, factor_origins: bool) -> hir::IconHir {
+    transform_with(lir, factor_origins, Compat::default())
+}
+
+/// Turn the LIR back into operations, which resolve to the same elements with the semantics
+pub fn transform_with(lir: &lir::IconLir, factor_origins: bool, compat: Compat) -> hir::IconHir {
+    let mut trans = Transformer {
+        operations: Vec::new(),
+        output: Resolver::new(compat),
+        errors: Vec::new(),
+    };
+
+    for (el_i, el) in lir.elements.iter().enumerate() {
+        if factor_origins {
+            trans.factor_origin(&lir.elements[el_i..]);
+        }
+
+        trans.process(el);
+    }
 
-pub fn transform(lir: &lir::IconLir) -> hir::IconHir {
     hir::IconHir {
-        operations: lir
-            .elements
-            .iter()
-            .scan(Transformer::default(), |trans, el| {
-                Some(match &el.kind {
-                    // TODO detect `cont`s
-                    lir::ElementKind::Line(line) => trans.process(
-                        el,
-                        line.width,
-                        hir::OperationKind::DrawLine(hir::DrawLine {
-                            start: line.start,
-                            end: line.end,
-                        }),
-                    ),
-                    lir::ElementKind::Disk(disk) => trans.process(
-                        el,
-                        disk.radius * 2.,
-                        hir::OperationKind::DrawDisk(hir::DrawDisk {
-                            centre: disk.centre,
-                        }),
-                    ),
-                    lir::ElementKind::Circle(circle) => trans.process(
-                        el,
-                        circle.width,
-                        hir::OperationKind::DrawArc(hir::DrawArc {
-                            centre: circle.centre,
-                            radius: circle.radius,
-                            direction: 0.,
-                            arm_angle: 180.,
-                        }),
-                    ),
-                    lir::ElementKind::Rectangle(rectangle) => trans.process(
-                        el,
-                        rectangle.outline_width,
-                        hir::OperationKind::DrawRectangle(hir::DrawRectangle {
-                            centre: rectangle.bottom_left + rectangle.sizes / 2.,
-                            sizes: rectangle.sizes,
-                            filled: rectangle.filled,
-                        }),
-                    ),
-                    lir::ElementKind::Triangle(triangle) => trans.process(
-                        el,
-                        triangle.outline_width,
-                        hir::OperationKind::DrawTriangle(hir::DrawTriangle {
-                            a: triangle.a,
-                            b: triangle.b,
-                            c: triangle.c,
-                        }),
-                    ),
-                    lir::ElementKind::Arc(arc) => trans.process(
-                        el,
-                        arc.width,
-                        hir::OperationKind::DrawArc({
-                            let direction = std::f32::consts::FRAC_PI_2
-                                - arc.start_angle.midpoint(arc.end_angle);
-                            let arm_angle = (arc.end_angle - arc.start_angle) / 2.;
-
-                            hir::DrawArc {
-                                centre: arc.centre,
-                                radius: arc.radius,
-                                direction: direction.to_degrees() / 10.,
-                                arm_angle: arm_angle.to_degrees(),
-                            }
-                        }),
-                    ),
-                    lir::ElementKind::Ellipse(ellipse) => trans.process(
-                        el,
-                        ellipse.outline_width,
-                        hir::OperationKind::DrawEllipse(hir::DrawEllipse {
-                            centre: ellipse.centre,
-                            major: ellipse.axis.x,
-                            ratio: ellipse.axis.y / ellipse.axis.x,
-                            direction: -ellipse.direction.to_degrees(),
-                        }),
-                    ),
-                    lir::ElementKind::Curve(curve) => trans.process(
-                        el,
-                        curve.width,
-                        hir::OperationKind::DrawCurve(hir::DrawCurve {
-                            start: curve.start,
-                            control: curve.control,
-                            end: curve.end,
-                        }),
-                    ),
-                })
-            })
-            .flatten()
-            .collect(),
+        operations: trans.operations,
     }
 }
 
-#[derive(Debug, Clone)]
 struct Transformer {
-    colour: Colour,
-    width: f32,
+    operations: Vec<hir::Operation>,
+    output: Resolver,
+    errors: Vec<rotur_icn_resolver::Error>,
 }
 
-impl Default for Transformer {
-    fn default() -> Self {
-        Self {
-            colour: Colour::WHITE,
-            width: 10.,
+impl Transformer {
+    fn emit(&mut self, kind: hir::OperationKind) {
+        let op = hir::Operation {
+            cmd_pos: CMD_POS,
+            kind,
+        };
+
+        self.output
+            .step(&mut self.errors, self.operations.len(), &op);
+        self.operations.push(op);
+    }
+
+    fn process(&mut self, el: &lir::Element) {
+        let width = get_width(el);
+
+        if self.output.width.to_bits() != width.to_bits() {
+            self.emit(hir::OperationKind::SetWidth(hir::SetWidth { value: width }));
+        }
+
+        if self.output.colour != el.colour {
+            self.emit(hir::OperationKind::SetColour(hir::SetColour {
+                value: el.colour,
+            }));
+        }
+
+        if let Some(kind) = find_exact(&self.output, el) {
+            self.emit(kind);
+            return;
+        }
+
+        if self.output.origin != Vector::ZERO {
+            let mut reset = self.output.clone();
+            reset.origin = Vector::ZERO;
+
+            if let Some(kind) = find_exact(&reset, el) {
+                self.emit(hir::OperationKind::ResetCentre(hir::ResetCentre));
+                self.emit(kind);
+                return;
+            }
         }
+
+        // no exact form exists, so stay as close as it gets
+        self.emit(get_relative(el, &self.output));
     }
-}
 
-impl Transformer {
-    fn process(
-        &mut self,
-        el: &lir::Element,
-        width: f32,
-        op: hir::OperationKind,
-    ) -> ArrayVec<hir::Operation, 3> {
-        // XXX address this somehow?
-        let cmd_pos = (token::Loc::default(), token::Loc::default());
-
-        let mut buf = ArrayVec::<_, 3>::new();
-
-        // FIXME relatively compare floats
-        if (self.width - width).abs() > 1e-7 {
-            self.width = width;
-
-            buf.push(hir::Operation {
-                cmd_pos,
-                kind: hir::OperationKind::SetWidth(hir::SetWidth { value: width }),
-            });
+    /// Move the centre, if the upcoming elements come out shorter, even with the move
+    fn factor_origin(&mut self, upcoming: &[lir::Element]) {
+        let current = self.output.origin;
+        let window = &upcoming[..upcoming.len().min(ORIGIN_LOOKAHEAD)];
+
+        let stay_len = self.window_len(None, window);
+
+        let best = [get_anchor(&upcoming[0]), Vector::ZERO]
+            .into_iter()
+            .filter(|&target| target != current)
+            .filter_map(|target| {
+                if target == Vector::ZERO {
+                    return Some(hir::OperationKind::ResetCentre(hir::ResetCentre));
+                }
+
+                let change = target - current;
+
+                // the resolver must land exactly on the target
+                (current + change == target)
+                    .then_some(hir::OperationKind::MoveCentre(hir::MoveCentre { change }))
+            })
+            .map(|kind| (self.window_len(Some(kind.clone()), window), kind))
+            .min_by_key(|(len, _)| *len);
+
+        if let Some((len, kind)) = best
+            && len < stay_len
+        {
+            self.emit(kind);
+        }
+    }
+
+    /// Length of the source the elements would be printed as, after the operation
+    fn window_len(&self, first: Option<hir::OperationKind>, window: &[lir::Element]) -> usize {
+        let mut trans = Transformer {
+            operations: Vec::new(),
+            output: self.output.clone(),
+            errors: Vec::new(),
+        };
+
+        if let Some(kind) = first {
+            trans.emit(kind);
         }
 
-        if self.colour != el.colour {
-            self.colour = el.colour;
+        for el in window {
+            trans.process(el);
+        }
 
-            buf.push(hir::Operation {
-                cmd_pos,
-                kind: hir::OperationKind::SetColour(hir::SetColour { value: el.colour }),
-            });
+        stringify(
+            &convert(&hir::IconHir {
+                operations: trans.operations,
+            }),
+            true,
+        )
+        .len()
+    }
+}
+
+/// Find an operation, which draws exactly the element, preferring continued lines
+fn find_exact(resolver: &Resolver, el: &lir::Element) -> Option<hir::OperationKind> {
+    let cont = match &el.kind {
+        lir::ElementKind::Line(line) if resolver.last_point.is_some() => {
+            Some(hir::OperationKind::ContinueLine(hir::ContinueLine {
+                next: line.end - resolver.origin,
+            }))
         }
+        _ => None,
+    };
+
+    cont.into_iter()
+        .chain([get_relative(el, resolver)])
+        .find(|kind| {
+            let op = hir::Operation {
+                cmd_pos: CMD_POS,
+                kind: kind.clone(),
+            };
+
+            resolver.clone().step(&mut Vec::new(), 0, &op).as_ref() == Some(el)
+        })
+}
+
+/// The width the resolver must be at, to draw the element
+fn get_width(el: &lir::Element) -> Number {
+    match &el.kind {
+        lir::ElementKind::Line(line) => line.width,
+        lir::ElementKind::Disk(disk) => disk.radius * 2.,
+        lir::ElementKind::Circle(circle) => circle.width,
+        lir::ElementKind::Rectangle(rectangle) => rectangle.outline_width,
+        lir::ElementKind::Triangle(triangle) => triangle.outline_width,
+        lir::ElementKind::Arc(arc) => arc.width,
+        lir::ElementKind::Ellipse(ellipse) => ellipse.outline_width,
+        lir::ElementKind::Curve(curve) => curve.width,
+    }
+}
+
+/// A whole point next to the element, which the centre may be moved to
+fn get_anchor(el: &lir::Element) -> Vector {
+    let point = match &el.kind {
+        lir::ElementKind::Line(line) => line.start,
+        lir::ElementKind::Disk(lir::Disk { centre, .. })
+        | lir::ElementKind::Circle(lir::Circle { centre, .. })
+        | lir::ElementKind::Arc(lir::Arc { centre, .. })
+        | lir::ElementKind::Ellipse(lir::Ellipse { centre, .. }) => *centre,
+        lir::ElementKind::Rectangle(rectangle) => rectangle.bottom_left,
+        lir::ElementKind::Triangle(triangle) => triangle.a,
+        lir::ElementKind::Curve(curve) => curve.start,
+    };
+
+    Vector {
+        x: point.x.round(),
+        y: point.y.round(),
+    }
+}
+
+/// The drawing operation for the element, relative to the resolver's origin
+fn get_relative(el: &lir::Element, resolver: &Resolver) -> hir::OperationKind {
+    let origin = resolver.origin;
 
-        buf.push(hir::Operation { cmd_pos, kind: op });
+    match &el.kind {
+        lir::ElementKind::Line(line) => hir::OperationKind::DrawLine(hir::DrawLine {
+            start: line.start - origin,
+            end: line.end - origin,
+        }),
+        lir::ElementKind::Disk(disk) => hir::OperationKind::DrawDisk(hir::DrawDisk {
+            centre: disk.centre - origin,
+        }),
+        lir::ElementKind::Circle(circle) => hir::OperationKind::DrawArc(hir::DrawArc {
+            centre: circle.centre - origin,
+            radius: circle.radius,
+            direction: 0.,
+            arm_angle: 180.,
+        }),
+        lir::ElementKind::Rectangle(rectangle) => {
+            // the HIR holds half of the sizes
+            let sizes = rectangle.sizes / 2.;
+
+            hir::OperationKind::DrawRectangle(hir::DrawRectangle {
+                centre: rectangle.bottom_left + sizes - origin,
+                sizes,
+                filled: rectangle.filled,
+            })
+        }
+        lir::ElementKind::Triangle(triangle) => {
+            hir::OperationKind::DrawTriangle(hir::DrawTriangle {
+                a: triangle.a - origin,
+                b: triangle.b - origin,
+                c: triangle.c - origin,
+            })
+        }
+        lir::ElementKind::Arc(arc) => hir::OperationKind::DrawArc({
+            let direction = std::f32::consts::FRAC_PI_2 - arc.start_angle.midpoint(arc.end_angle);
+            let arm_angle = (arc.end_angle - arc.start_angle) / 2.;
 
-        buf
+            hir::DrawArc {
+                centre: arc.centre - origin,
+                radius: arc.radius,
+                direction: direction.to_degrees() / resolver.compat().arc_direction_scale,
+                arm_angle: arm_angle.to_degrees(),
+            }
+        }),
+        lir::ElementKind::Ellipse(ellipse) => hir::OperationKind::DrawEllipse(hir::DrawEllipse {
+            centre: ellipse.centre - origin,
+            major: ellipse.axis.x,
+            ratio: ellipse.axis.y / ellipse.axis.x,
+            direction: -ellipse.direction.to_degrees(),
+        }),
+        lir::ElementKind::Curve(curve) => hir::OperationKind::DrawCurve(hir::DrawCurve {
+            start: curve.start - origin,
+            control: curve.control - origin,
+            end: curve.end - origin,
+        }),
     }
 }
@@ -0,0 +1,57 @@
+use rotur_icn_pipeline::{Compat, Dialect, LintConfig};
+use rotur_icn_printer::{Layout, Style, stringify_styled};
+
+fn reprint(src: &str, layout: Layout) -> String {
+    let (icon, _, _, errors) = rotur_icn_pipeline::process_with(
+        src,
+        Dialect { comments: true },
+        Compat::default(),
+        &LintConfig::default(),
+    );
+    assert!(errors.is_empty(), "{src:?} should be valid");
+
+    stringify_styled(
+        &icon,
+        &Style {
+            layout,
+            ..Style::default()
+        },
+    )
+}
+
+#[test]
+fn comments_and_blank_lines_survive_reprinting() {
+    let src = "\
+// a red dot
+// in the centre
+
+c #ff0000
+dot 0 0 // right here
+
+// then a line
+line 0 0 1 1
+// the end
+";
+
+    assert_eq!(reprint(src, Layout::PerLine), src);
+}
+
+#[test]
+fn comments_end_their_line_in_a_single_line_layout() {
+    let src = "// a dot\nc #ff0000 dot 0 0 // here\nline 0 0 1 1\n";
+
+    assert_eq!(
+        reprint(src, Layout::Oneline),
+        "// a dot\nc #ff0000 dot 0 0 // here\nline 0 0 1 1 "
+    );
+}
+
+#[test]
+fn whitespace_is_normalised_around_comments() {
+    let src = "  c   #ff0000\n\n\n\ndot 0    0   // here   \n\n";
+
+    assert_eq!(
+        reprint(src, Layout::PerLine),
+        "c #ff0000\n\ndot 0 0 // here\n"
+    );
+}
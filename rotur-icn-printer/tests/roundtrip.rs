@@ -0,0 +1,94 @@
+use std::f32::consts::TAU;
+
+use rand::{SeedableRng as _, distr::Distribution as _, rngs::StdRng};
+
+use rotur_icn_lowerer::hir;
+use rotur_icn_pipeline::{Dialect, LintConfig};
+use rotur_icn_printer::{print_hir, transform_with};
+use rotur_icn_rand::IcnSampler;
+use rotur_icn_resolver::{Compat, lir};
+use rotur_icn_units::Number;
+
+/// How far angles may drift, as they're converted into & back from `cutcircle`'s & `ellipse`'s arguments
+const ANGLE_TOLERANCE: Number = 1e-4;
+
+fn same_angle(a: Number, b: Number) -> bool {
+    let diff = (a - b).rem_euclid(TAU);
+    diff.min(TAU - diff) <= ANGLE_TOLERANCE
+}
+
+#[expect(clippy::float_cmp, reason = "all but the angles are printed exactly")]
+fn same_element(a: &lir::Element, b: &lir::Element) -> bool {
+    a.colour == b.colour
+        && match (&a.kind, &b.kind) {
+            (lir::ElementKind::Arc(a), lir::ElementKind::Arc(b)) => {
+                a.centre == b.centre
+                    && a.radius == b.radius
+                    && a.width == b.width
+                    && same_angle(a.start_angle, b.start_angle)
+                    && same_angle(a.end_angle, b.end_angle)
+            }
+            (lir::ElementKind::Ellipse(a), lir::ElementKind::Ellipse(b)) => {
+                a.centre == b.centre
+                    && a.axis == b.axis
+                    && a.outline_width == b.outline_width
+                    && same_angle(a.direction, b.direction)
+            }
+            (a, b) => a == b,
+        }
+}
+
+fn check_round_trip(factor_origins: bool, compat: Compat) {
+    let sampler = IcnSampler::default();
+    let mut rng = StdRng::seed_from_u64(0x1c4);
+
+    for _ in 0..200 {
+        let icon: hir::IconHir = sampler.sample(&mut rng);
+        let (icon, _) = rotur_icn_resolver::resolve_with(&icon, compat);
+
+        let src = print_hir(&transform_with(&icon, factor_origins, compat), false);
+        let (_, _, round_tripped, errors) = rotur_icn_pipeline::process_with(
+            &src,
+            Dialect::default(),
+            compat,
+            &LintConfig::default(),
+        );
+
+        assert!(errors.is_empty(), "errors in the printed ICN\n{src}");
+        assert_eq!(
+            icon.elements.len(),
+            round_tripped.elements.len(),
+            "element counts differ for\n{src}"
+        );
+
+        for (el, round_tripped_el) in icon.elements.iter().zip(&round_tripped.elements) {
+            assert!(
+                same_element(el, round_tripped_el),
+                "{el:?} resolved back as {round_tripped_el:?} from\n{src}"
+            );
+        }
+    }
+}
+
+#[test]
+fn printed_lir_resolves_back() {
+    check_round_trip(false, Compat::default());
+}
+
+#[test]
+fn printed_lir_with_factored_origins_resolves_back() {
+    check_round_trip(true, Compat::default());
+}
+
+#[test]
+fn printed_lir_resolves_back_with_other_semantics() {
+    check_round_trip(
+        true,
+        Compat {
+            default_width: 2.,
+            arc_direction_scale: 1.,
+            offset_continued_lines: false,
+            ..Compat::default()
+        },
+    );
+}
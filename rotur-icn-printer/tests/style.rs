@@ -0,0 +1,119 @@
+use rand::{SeedableRng as _, distr::Distribution as _, rngs::StdRng};
+
+use rotur_icn_lowerer::hir;
+use rotur_icn_printer::{Layout, Style, print_hir, stringify_styled};
+use rotur_icn_rand::IcnSampler;
+
+fn reprint(src: &str, style: &Style) -> String {
+    let (icon, _, _, errors) = rotur_icn_pipeline::process(src);
+    assert!(
+        errors.lexing.is_empty() && errors.parsing.is_empty(),
+        "{src:?} should parse"
+    );
+
+    stringify_styled(&icon, style)
+}
+
+fn precision(precision: usize) -> Style {
+    Style {
+        layout: Layout::Oneline,
+        precision: Some(precision),
+        ..Style::default()
+    }
+}
+
+#[test]
+fn numbers_are_rounded_with_trailing_zeros_trimmed() {
+    assert_eq!(
+        reprint("line 1.234 2.5 3.999 0.1", &precision(2)),
+        "line 1.23 2.5 4 0.1 "
+    );
+
+    // only the decimal places are trimmed
+    assert_eq!(
+        reprint("line 10 100 2.5 0.4", &precision(0)),
+        "line 10 100 2 0 "
+    );
+}
+
+#[test]
+fn numbers_rounded_to_zero_lose_their_sign() {
+    assert_eq!(reprint("dot -0.001 -0.4", &precision(2)), "dot 0 -0.4 ");
+    assert_eq!(reprint("dot -0.4 -0.6", &precision(0)), "dot 0 -1 ");
+}
+
+#[test]
+fn numbers_are_left_alone_without_a_precision() {
+    let style = Style {
+        layout: Layout::Oneline,
+        ..Style::default()
+    };
+
+    assert_eq!(reprint("dot 1.23456 -0.001", &style), "dot 1.23456 -0.001 ");
+}
+
+#[test]
+fn colours_are_shortened_only_when_exact() {
+    let style = Style {
+        layout: Layout::Oneline,
+        short_colours: true,
+        ..Style::default()
+    };
+
+    assert_eq!(
+        reprint("c #ffaa00 c #ffab00 c #fff", &style),
+        "c #fa0 c #ffab00 c #fff "
+    );
+
+    let style = Style {
+        short_colours: false,
+        ..style
+    };
+
+    assert_eq!(reprint("c #fa0 c #ffab00", &style), "c #ffaa00 c #ffab00 ");
+}
+
+#[test]
+fn grouped_layout_starts_a_line_at_each_state_change() {
+    let style = Style {
+        layout: Layout::Grouped,
+        ..Style::default()
+    };
+
+    assert_eq!(
+        reprint(
+            "c #f00 w 2 dot 0 0 line 0 0 1 1 c #0f0 dot 1 1 w 1 c #00f dot 2 2",
+            &style
+        ),
+        "c #ff0000 w 2 dot 0 0 line 0 0 1 1\nc #00ff00 dot 1 1\nw 1 c #0000ff dot 2 2\n"
+    );
+}
+
+#[test]
+fn reprinting_is_idempotent() {
+    let styles = [Layout::Oneline, Layout::PerLine, Layout::Grouped]
+        .into_iter()
+        .flat_map(|layout| {
+            [None, Some(0), Some(2)].map(|precision| Style {
+                layout,
+                precision,
+                short_colours: precision.is_some(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let sampler = IcnSampler::default();
+    let mut rng = StdRng::seed_from_u64(0x1c4);
+
+    for _ in 0..50 {
+        let icon: hir::IconHir = sampler.sample(&mut rng);
+        let src = print_hir(&icon, false);
+
+        for style in &styles {
+            let once = reprint(&src, style);
+            let twice = reprint(&once, style);
+
+            assert_eq!(once, twice, "{style:?} isn't idempotent for\n{src}");
+        }
+    }
+}
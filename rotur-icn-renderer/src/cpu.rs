@@ -9,18 +9,40 @@ mod colour;
 mod curve;
 mod disk;
 mod ellipse;
+mod error;
+mod grid;
 mod line;
 mod maths;
 mod rectangle;
 mod shape;
 mod triangle;
 
+pub use error::RenderError;
+
+/// Pixels a canvas may have by default, when rendering through the `try_` methods
+pub const DEFAULT_PIXEL_BUDGET: usize = 1 << 26;
+
+/// How the rendered colours are stored in the output buffer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AlphaMode {
+    /// Colour channels are independent of alpha
+    #[default]
+    Straight,
+    /// Colour channels are already multiplied by alpha
+    Premultiplied,
+}
+
 pub struct Renderer {
     icon: Option<ComputedShapesBundle>,
     pub background_colour: Colour,
     pub canvas: Vector,
     pub scaling: Number,
     pub camera_pos: Vector,
+    /// Blend shapes by partial pixel coverage instead of hard-testing pixel centres
+    pub anti_aliasing: bool,
+    pub alpha_mode: AlphaMode,
+    /// Pixels the canvas may have at most, checked by the `try_` methods
+    pub pixel_budget: usize,
 }
 
 impl Default for Renderer {
@@ -31,6 +53,9 @@ impl Default for Renderer {
             canvas: Vector { x: 20., y: 20. },
             scaling: 1.,
             camera_pos: Vector::ZERO,
+            anti_aliasing: false,
+            alpha_mode: AlphaMode::Straight,
+            pixel_budget: DEFAULT_PIXEL_BUDGET,
         }
     }
 }
@@ -47,6 +72,9 @@ impl Renderer {
             scaling,
             camera_pos,
             background_colour,
+            anti_aliasing: false,
+            alpha_mode: AlphaMode::Straight,
+            pixel_budget: DEFAULT_PIXEL_BUDGET,
             icon: None,
         }
     }
@@ -62,11 +90,22 @@ impl Renderer {
         )
     }
 
+    /// Like [`Renderer::new_buf`], but the canvas is checked first
+    ///
+    /// # Errors
+    ///
+    /// If a parameter isn't finite or the canvas is over the pixel budget.
+    pub fn try_new_buf(&self) -> Result<(Vec<u8>, (usize, usize)), RenderError> {
+        let size = self.try_scaled_buf_size()?;
+        Ok((vec![0; size.0 * size.1 * 4], size))
+    }
+
+    /// Size of the canvas in pixels, which saturates for too large ones,
+    /// see [`Renderer::try_scaled_buf_size`] to forbid them
     #[expect(clippy::cast_possible_truncation)]
     #[expect(clippy::cast_sign_loss)]
     pub fn scaled_buf_size(&self) -> (usize, usize) {
         let scaled = self.canvas * self.scaling;
-        // FIXME forbid too large buf sizes
         (
             scaled.x.abs().round() as usize,
             scaled.y.abs().round() as usize,
@@ -78,14 +117,54 @@ impl Renderer {
         scaled.0 * scaled.1 * 4
     }
 
+    /// Size of the canvas in pixels, which is within the pixel budget
+    ///
+    /// # Errors
+    ///
+    /// If a parameter isn't finite or the canvas is over the pixel budget.
+    pub fn try_scaled_buf_size(&self) -> Result<(usize, usize), RenderError> {
+        for (name, value) in [
+            ("canvas width", self.canvas.x),
+            ("canvas height", self.canvas.y),
+            ("scaling", self.scaling),
+            ("camera x", self.camera_pos.x),
+            ("camera y", self.camera_pos.y),
+        ] {
+            if !value.is_finite() {
+                return Err(RenderError::NonFiniteParameter(name));
+            }
+        }
+
+        let (width, height) = self.scaled_buf_size();
+
+        // the bytes have to fit as well, which the budget may not ensure
+        let fits = width
+            .checked_mul(height)
+            .filter(|&pixels| pixels <= self.pixel_budget)
+            .and_then(|pixels| pixels.checked_mul(4))
+            .is_some_and(|bytes| isize::try_from(bytes).is_ok());
+
+        if fits {
+            Ok((width, height))
+        } else {
+            Err(RenderError::CanvasTooLarge {
+                width,
+                height,
+                budget: self.pixel_budget,
+            })
+        }
+    }
+
     /// Render the loaded ICN into an RGBA buffer
     ///
+    /// With the `parallel` feature, the buffer is split into bands of rows
+    /// rendered across all available threads.
+    ///
     /// # Panics
     ///
     /// - If buffer length is not of correct size (4 bytes per pixel)
     ///
     /// - If no ICN is loaded
-    #[expect(clippy::cast_precision_loss)]
     pub fn render(&mut self, buf: &mut [u8]) {
         assert_eq!(
             buf.len(),
@@ -97,35 +176,194 @@ impl Renderer {
             .icon
             .as_ref()
             .expect("icon should have been loaded by this point");
-        let bg_colour = self.background_colour.into();
 
-        let scaled_buf_size = self.scaled_buf_size();
+        self.draw(icon, buf, self.scaled_buf_size());
+    }
+
+    /// Like [`Renderer::render`], but everything is checked first
+    ///
+    /// # Errors
+    ///
+    /// If no ICN is loaded, a parameter isn't finite, the canvas is over the pixel budget
+    /// or the buffer is of a wrong size.
+    pub fn try_render(&mut self, buf: &mut [u8]) -> Result<(), RenderError> {
+        let icon = self.icon.as_ref().ok_or(RenderError::NoIconLoaded)?;
+        let scaled_buf_size = self.try_scaled_buf_size()?;
+
+        let expected = scaled_buf_size.0 * scaled_buf_size.1 * 4;
+        if buf.len() != expected {
+            return Err(RenderError::BadBufferSize {
+                expected,
+                found: buf.len(),
+            });
+        }
+
+        self.draw(icon, buf, scaled_buf_size);
+
+        Ok(())
+    }
+
+    fn draw(&self, icon: &ComputedShapesBundle, buf: &mut [u8], scaled_buf_size: (usize, usize)) {
+        #[cfg(feature = "parallel")]
+        self.render_parallel(icon, buf, scaled_buf_size);
+
+        #[cfg(not(feature = "parallel"))]
+        self.render_rows(icon, buf, 0, scaled_buf_size);
+    }
+
+    #[cfg(feature = "parallel")]
+    fn render_parallel(
+        &self,
+        icon: &ComputedShapesBundle,
+        buf: &mut [u8],
+        scaled_buf_size: (usize, usize),
+    ) {
+        const BAND_ROWS: usize = 16;
+
+        if buf.is_empty() {
+            return;
+        }
+
+        let threads = std::thread::available_parallelism().map_or(1, std::num::NonZero::get);
+        let bands = std::sync::Mutex::new(
+            buf.chunks_mut(scaled_buf_size.0 * 4 * BAND_ROWS)
+                .enumerate(),
+        );
+
+        std::thread::scope(|s| {
+            for _ in 0..threads {
+                s.spawn(|| {
+                    loop {
+                        // the guard must be dropped before rendering, so that others can take bands
+                        let Some((band_i, band)) = bands
+                            .lock()
+                            .expect("no thread should panic while holding the lock")
+                            .next()
+                        else {
+                            break;
+                        };
+
+                        self.render_rows(icon, band, band_i * BAND_ROWS, scaled_buf_size);
+                    }
+                });
+            }
+        });
+    }
+
+    /// Render a band of whole rows, starting at `y_start`
+    #[expect(clippy::cast_precision_loss)]
+    fn render_rows(
+        &self,
+        icon: &ComputedShapesBundle,
+        rows: &mut [u8],
+        y_start: usize,
+        scaled_buf_size: (usize, usize),
+    ) {
+        // an empty canvas has no rows to split into
+        if rows.is_empty() {
+            return;
+        }
+
+        let bg_colour = self.background_colour.into();
 
-        // FIXME forbid too large buf sizes
         let rel_x_offset = (scaled_buf_size.0 / 2) as Number;
         let rel_y_offset = (scaled_buf_size.1 / 2) as Number;
 
-        for y in 0..scaled_buf_size.1 {
-            for x in 0..scaled_buf_size.0 {
-                let pixel_i = (y * scaled_buf_size.0 + x) * 4;
-                let pixel = &mut buf[pixel_i..pixel_i + 4];
+        let pixel_size = 1. / self.scaling;
+
+        for (row_i, row) in rows.chunks_exact_mut(scaled_buf_size.0 * 4).enumerate() {
+            let y = y_start + row_i;
 
+            for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
                 let rel_x = (x as Number) - rel_x_offset;
                 let rel_y = -(y as Number) + rel_y_offset;
 
                 let rel_pos = Vector { x: rel_x, y: rel_y } / self.scaling + self.camera_pos;
 
-                let new_col = icon
-                    .shapes
-                    .iter()
-                    .rev()
-                    .find_map(|sp| sp.test_with_colour(rel_pos))
-                    .unwrap_or(bg_colour);
+                let new_col = if self.anti_aliasing {
+                    icon.blended_colour_at(rel_pos, pixel_size, bg_colour)
+                } else {
+                    icon.colour_at(rel_pos, bg_colour)
+                };
 
-                let new_pixel = new_col.to_bytes();
+                let new_pixel = match self.alpha_mode {
+                    AlphaMode::Straight => new_col.to_straight(),
+                    AlphaMode::Premultiplied => new_col.to_premultiplied(),
+                }
+                .to_bytes();
 
                 pixel.copy_from_slice(&new_pixel);
             }
         }
     }
 }
+
+#[cfg(all(test, feature = "parallel"))]
+mod tests {
+    use rand::{Rng as _, SeedableRng as _, distr::Distribution as _, rngs::StdRng};
+    use rotur_icn_lowerer::hir;
+    use rotur_icn_rand::IcnSampler;
+    use rotur_icn_units::{Colour, Vector};
+
+    use super::{AlphaMode, Renderer};
+
+    /// Canvases in pixels at a scaling of 3, mostly not a multiple of the bands' rows
+    const CANVASES: [(usize, usize); 5] = [(39, 51), (21, 15), (60, 60), (1, 1), (8, 33)];
+
+    #[test]
+    fn parallel_render_is_the_serial_one() {
+        let mut sampler = IcnSampler::default();
+        sampler.full_colour = true;
+        let mut rng = StdRng::seed_from_u64(0x1c4);
+
+        for i in 0..16 {
+            let icon: hir::IconHir = sampler.sample(&mut rng);
+            let (icon, _) = rotur_icn_resolver::resolve(&icon);
+
+            let (width, height) = CANVASES[i % CANVASES.len()];
+
+            #[expect(clippy::cast_precision_loss)]
+            let mut renderer = Renderer::new(
+                Vector {
+                    x: width as f32,
+                    y: height as f32,
+                } / 3.,
+                3.,
+                Vector {
+                    x: rng.random_range(-5.0..5.0),
+                    y: rng.random_range(-5.0..5.0),
+                },
+                Colour {
+                    r: 0x10,
+                    g: 0x20,
+                    b: 0x30,
+                    a: 0x80,
+                },
+            );
+            renderer.load(&icon);
+            renderer.anti_aliasing = i % 2 == 0;
+            renderer.alpha_mode = if i % 4 < 2 {
+                AlphaMode::Straight
+            } else {
+                AlphaMode::Premultiplied
+            };
+
+            let size = renderer.scaled_buf_size();
+            assert_eq!(size, (width, height));
+
+            let bundle = renderer.icon.as_ref().expect("the icon is loaded");
+
+            let mut parallel = vec![0; width * height * 4];
+            renderer.render_parallel(bundle, &mut parallel, size);
+
+            let mut serial = vec![0; width * height * 4];
+            renderer.render_rows(bundle, &mut serial, 0, size);
+
+            assert!(
+                parallel == serial,
+                "renders of {width}×{height} differ, with anti-aliasing: {}\n{icon}",
+                renderer.anti_aliasing,
+            );
+        }
+    }
+}
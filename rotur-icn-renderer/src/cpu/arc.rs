@@ -12,6 +12,9 @@ pub struct Arc {
     outline: f32,
     inner_outline: f32,
     outer_outline: f32,
+    halfwidth: f32,
+    inner_radius: f32,
+    outer_radius: f32,
     is_open: bool,
 }
 
@@ -24,7 +27,7 @@ impl Arc {
         );
 
         // TODO add internal culling box
-        let bb = crate::fitter::arc::get_bounds(el);
+        let bb = el.bounds();
 
         let start = Vector::new_from_length(el.radius, el.start_angle);
         let middle = Vector::new_normal(el.start_angle.midpoint(el.end_angle));
@@ -47,6 +50,26 @@ impl Arc {
             outline,
             inner_outline,
             outer_outline,
+            halfwidth,
+            inner_radius: inner_outline.sqrt(),
+            outer_radius: outer_outline.sqrt(),
+        }
+    }
+
+    /// Squared distance to the nearest arm's end, or `None` if within the arc's sector
+    fn ends_distance_sq(&self, to_pos: Vector) -> Option<f32> {
+        let inside_start = to_pos.cross(self.start).is_sign_negative();
+        let inside_end = to_pos.cross(self.end).is_sign_positive();
+
+        match (self.is_open, inside_start, inside_end) {
+            (_, true, true) | (true, true, false) | (true, false, true) => None,
+            (false, false, true) => Some((to_pos - self.start).length_sq()),
+            (false, true, false) => Some((to_pos - self.end).length_sq()),
+            (_, false, false) => {
+                let start_closer = to_pos.cross(self.middle).is_sign_positive();
+
+                Some((to_pos - if start_closer { self.start } else { self.end }).length_sq())
+            }
         }
     }
 }
@@ -59,23 +82,22 @@ impl Shape for Arc {
 
         let to_pos = pos - self.centre;
 
-        let inside_start = to_pos.cross(self.start).is_sign_negative();
-        let inside_end = to_pos.cross(self.end).is_sign_positive();
-
-        let d = match (self.is_open, inside_start, inside_end) {
-            (_, true, true) | (true, true, false) | (true, false, true) => {
-                let d = to_pos.length_sq();
-                return self.inner_outline <= d && d <= self.outer_outline;
-            }
-            (false, false, true) => (to_pos - self.start).length_sq(),
-            (false, true, false) => (to_pos - self.end).length_sq(),
-            (_, false, false) => {
-                let start_closer = to_pos.cross(self.middle).is_sign_positive();
+        if let Some(d) = self.ends_distance_sq(to_pos) {
+            d <= self.outline
+        } else {
+            let d = to_pos.length_sq();
+            self.inner_outline <= d && d <= self.outer_outline
+        }
+    }
 
-                (to_pos - if start_closer { self.start } else { self.end }).length_sq()
-            }
-        };
+    fn distance(&self, pos: Vector) -> f32 {
+        let to_pos = pos - self.centre;
 
-        d <= self.outline
+        if let Some(d) = self.ends_distance_sq(to_pos) {
+            d.sqrt() - self.halfwidth
+        } else {
+            let d = to_pos.length();
+            (self.inner_radius - d).max(d - self.outer_radius)
+        }
     }
 }
@@ -7,6 +7,8 @@ pub struct Circle {
     centre: Vector,
     inner_outline: f32,
     outer_outline: f32,
+    inner_radius: f32,
+    outer_radius: f32,
 }
 
 impl Circle {
@@ -19,6 +21,8 @@ impl Circle {
             centre: el.centre,
             inner_outline,
             outer_outline,
+            inner_radius: inner_outline.sqrt(),
+            outer_radius: outer_outline.sqrt(),
         }
     }
 }
@@ -30,4 +34,10 @@ impl Shape for Circle {
 
         self.inner_outline <= d && d <= self.outer_outline
     }
+
+    fn distance(&self, pos: Vector) -> f32 {
+        let d = (pos - self.centre).length();
+
+        (self.inner_radius - d).max(d - self.outer_radius)
+    }
 }
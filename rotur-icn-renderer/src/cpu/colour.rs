@@ -19,3 +19,61 @@ impl From<Colour> for InternalColour {
         Self { r, g, b, a }
     }
 }
+
+/// Premultiplied colour with float channels in `0..=1`, used for compositing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PremultipliedColour {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl PremultipliedColour {
+    /// Composite a straight colour, scaled by its coverage, beneath the accumulated one
+    pub fn add_under(&mut self, col: InternalColour, coverage: f32) {
+        let a = f32::from(col.a) / 255. * coverage * (1. - self.a);
+
+        self.r += f32::from(col.r) / 255. * a;
+        self.g += f32::from(col.g) / 255. * a;
+        self.b += f32::from(col.b) / 255. * a;
+        self.a += a;
+    }
+
+    pub fn is_opaque(self) -> bool {
+        self.a >= 1.
+    }
+
+    pub fn to_premultiplied(self) -> InternalColour {
+        InternalColour {
+            r: to_channel(self.r),
+            g: to_channel(self.g),
+            b: to_channel(self.b),
+            a: to_channel(self.a),
+        }
+    }
+
+    pub fn to_straight(self) -> InternalColour {
+        if self.a <= 0. {
+            return InternalColour {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            };
+        }
+
+        InternalColour {
+            r: to_channel(self.r / self.a),
+            g: to_channel(self.g / self.a),
+            b: to_channel(self.b / self.a),
+            a: to_channel(self.a),
+        }
+    }
+}
+
+#[expect(clippy::cast_possible_truncation)]
+#[expect(clippy::cast_sign_loss)]
+fn to_channel(v: f32) -> u8 {
+    (v * 255.).round().clamp(0., 255.) as u8
+}
@@ -10,11 +10,12 @@ pub struct Curve {
     b: Vector,
     kk: f32,
     outline: f32,
+    halfwidth: f32,
 }
 
 impl Curve {
     pub fn new(el: &lir::Curve) -> Self {
-        let bb = crate::fitter::curve::get_bounds(el);
+        let bb = el.bounds();
         let a = el.control - el.start;
         let b = el.start - 2. * el.control + el.end;
         let kk = 1. / b.length_sq();
@@ -27,19 +28,14 @@ impl Curve {
             b,
             kk,
             outline,
+            halfwidth: el.width / 2.,
         }
     }
-}
 
-impl Shape for Curve {
     // based on https://www.shadertoy.com/view/MlKcDD
     // TODO try using the faster apprx method + aggressive triangle culling
     #[expect(clippy::many_single_char_names)]
-    fn test(&self, pos: Vector) -> bool {
-        if !pos.within(self.bb) {
-            return false;
-        }
-
+    fn distance_sq(&self, pos: Vector) -> f32 {
         let c = 2. * self.a;
         let d = self.start - pos;
 
@@ -54,7 +50,7 @@ impl Shape for Curve {
         let q2 = q.powi(2);
         let h = q2 + 4. * p3;
 
-        let d = if h >= 0. {
+        if h >= 0. {
             let nq = -q;
 
             let h = h.sqrt().copysign(nq);
@@ -89,8 +85,20 @@ impl Shape for Curve {
             let d2 = q2.length_sq();
 
             d1.min(d2)
-        };
+        }
+    }
+}
+
+impl Shape for Curve {
+    fn test(&self, pos: Vector) -> bool {
+        if !pos.within(self.bb) {
+            return false;
+        }
+
+        self.distance_sq(pos) <= self.outline
+    }
 
-        d <= self.outline
+    fn distance(&self, pos: Vector) -> f32 {
+        self.distance_sq(pos).sqrt() - self.halfwidth
     }
 }
@@ -16,6 +16,11 @@ impl Disk {
             outline: el.radius,
         }
     }
+
+    pub fn bounds(&self) -> (Vector, Vector) {
+        let radius = self.outline.sqrt();
+        (self.centre - radius, self.centre + radius)
+    }
 }
 
 impl Shape for Disk {
@@ -25,4 +30,9 @@ impl Shape for Disk {
 
         d <= self.outline
     }
+
+    fn distance(&self, pos: Vector) -> f32 {
+        // mirrors `test`, which compares the squared distance against the radius
+        (pos - self.centre).length() - self.outline.sqrt()
+    }
 }
@@ -11,6 +11,7 @@ pub struct Ellipse {
     axis_inverse: Vector,
     axis_v: Vector,
     outline: f32,
+    halfwidth: f32,
 }
 
 impl Ellipse {
@@ -31,25 +32,20 @@ impl Ellipse {
 
         Self {
             // TODO add internal culling box
-            bb: crate::fitter::ellipse::get_bounds(el),
+            bb: el.bounds(),
             centre: el.centre,
             axis: el.axis,
             rotation_coefs: (-el.direction).sin_cos(),
             axis_inverse,
             axis_v,
             outline: el.outline_width.powi(2) / 4.,
+            halfwidth: el.outline_width / 2.,
         }
     }
-}
 
-impl Shape for Ellipse {
     // from https://www.shadertoy.com/view/tt3yz7
     // TODO find another algo which supports a rotated ellipse within itself
-    fn test(&self, pos: Vector) -> bool {
-        if !pos.within(self.bb) {
-            return false;
-        }
-
+    fn distance_sq(&self, pos: Vector) -> f32 {
         let p_abs = (pos - self.centre)
             .rotate_with_coefs(self.rotation_coefs)
             .abs();
@@ -63,8 +59,20 @@ impl Shape for Ellipse {
         }
 
         let nearest_abs = t * self.axis;
-        let d = (p_abs - nearest_abs).length_sq();
+        (p_abs - nearest_abs).length_sq()
+    }
+}
+
+impl Shape for Ellipse {
+    fn test(&self, pos: Vector) -> bool {
+        if !pos.within(self.bb) {
+            return false;
+        }
+
+        self.distance_sq(pos) <= self.outline
+    }
 
-        d <= self.outline
+    fn distance(&self, pos: Vector) -> f32 {
+        self.distance_sq(pos).sqrt() - self.halfwidth
     }
 }
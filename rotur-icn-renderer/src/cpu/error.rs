@@ -0,0 +1,41 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderError {
+    NoIconLoaded,
+    BadBufferSize {
+        expected: usize,
+        found: usize,
+    },
+    /// The canvas has more pixels than the budget allows, or than fit in memory at all
+    CanvasTooLarge {
+        width: usize,
+        height: usize,
+        budget: usize,
+    },
+    /// Which parameter is NaN or infinite
+    NonFiniteParameter(&'static str),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoIconLoaded => write!(f, "no ICN is loaded"),
+            Self::BadBufferSize { expected, found } => write!(
+                f,
+                "the buffer is {found} bytes long, but {expected} are needed"
+            ),
+            Self::CanvasTooLarge {
+                width,
+                height,
+                budget,
+            } => write!(
+                f,
+                "the canvas of {width}×{height} pixels is over the budget of {budget} pixels"
+            ),
+            Self::NonFiniteParameter(name) => write!(f, "the {name} isn't finite"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
@@ -0,0 +1,205 @@
+use rotur_icn_units::Vector;
+
+/// Max amount of cells along each axis
+const MAX_CELLS: usize = 32;
+
+/// A uniform grid over the shapes' bounds, which keeps for each cell
+/// indices of shapes whose bounds touch it, in z-order
+pub struct ShapeGrid {
+    bounds: (Vector, Vector),
+    cell_size: Vector,
+    cells_count: (usize, usize),
+    cells: Vec<Vec<usize>>,
+}
+
+impl ShapeGrid {
+    pub fn new(shapes_bounds: &[(Vector, Vector)]) -> Self {
+        let Some(bounds) = shapes_bounds
+            .iter()
+            .copied()
+            .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1)))
+        else {
+            return Self {
+                bounds: (Vector::ZERO, Vector::ZERO),
+                cell_size: Vector::new(1.),
+                cells_count: (0, 0),
+                cells: Vec::new(),
+            };
+        };
+
+        let size = bounds.1 - bounds.0;
+
+        let count_along = |size: f32| if size > 0. { MAX_CELLS } else { 1 };
+        let cells_count = (count_along(size.x), count_along(size.y));
+
+        #[expect(clippy::cast_precision_loss, reason = "counts are small")]
+        let cell_size = Vector {
+            x: if size.x > 0. {
+                size.x / cells_count.0 as f32
+            } else {
+                1.
+            },
+            y: if size.y > 0. {
+                size.y / cells_count.1 as f32
+            } else {
+                1.
+            },
+        };
+
+        let mut grid = Self {
+            bounds,
+            cell_size,
+            cells_count,
+            cells: vec![Vec::new(); cells_count.0 * cells_count.1],
+        };
+
+        for (shape_i, &(bl, tr)) in shapes_bounds.iter().enumerate() {
+            let (min_x, min_y) = grid.cell_of(bl);
+            let (max_x, max_y) = grid.cell_of(tr);
+
+            for y in min_y..=max_y {
+                for x in min_x..=max_x {
+                    grid.cells[y * cells_count.0 + x].push(shape_i);
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Indices of shapes, which may contain `pos`
+    pub fn at(&self, pos: Vector) -> &[usize] {
+        // an empty grid has no cells, even at its bounds
+        if self.cells.is_empty() || !pos.within(self.bounds) {
+            return &[];
+        }
+
+        let (x, y) = self.cell_of(pos);
+        &self.cells[y * self.cells_count.0 + x]
+    }
+
+    /// Indices of shapes, which may touch the square area around `pos`
+    ///
+    /// Returns `None`, if the area spans several cells.
+    pub fn around(&self, pos: Vector, half: f32) -> Option<&[usize]> {
+        let area = (pos - half, pos + half);
+
+        if self.cells.is_empty()
+            || area.1.x < self.bounds.0.x
+            || self.bounds.1.x < area.0.x
+            || area.1.y < self.bounds.0.y
+            || self.bounds.1.y < area.0.y
+        {
+            return Some(&[]);
+        }
+
+        let bl = self.cell_of(area.0);
+        let tr = self.cell_of(area.1);
+
+        (bl == tr).then(|| self.cells[bl.1 * self.cells_count.0 + bl.0].as_slice())
+    }
+
+    /// Cell's coords containing `pos`, clamped to the grid
+    #[expect(clippy::cast_possible_truncation)]
+    #[expect(clippy::cast_sign_loss)]
+    fn cell_of(&self, pos: Vector) -> (usize, usize) {
+        let rel = ((pos - self.bounds.0) / self.cell_size).max(Vector::ZERO);
+
+        (
+            (rel.x.floor() as usize).min(self.cells_count.0 - 1),
+            (rel.y.floor() as usize).min(self.cells_count.1 - 1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng as _, SeedableRng as _, distr::Distribution as _, rngs::StdRng};
+    use rotur_icn_lowerer::hir;
+    use rotur_icn_rand::IcnSampler;
+    use rotur_icn_units::Vector;
+
+    use crate::cpu::shape::{ComputedShapesBundle, Shape as _};
+
+    /// Shapes hit at `pos` through the grid, topmost first
+    fn hit_through_grid(bundle: &ComputedShapesBundle, pos: Vector) -> Vec<usize> {
+        let shapes = bundle.grid.at(pos).iter().rev().copied();
+        shapes.filter(|&i| bundle.shapes[i].test(pos)).collect()
+    }
+
+    /// Shapes hit at `pos` by scanning all of them, topmost first
+    fn hit_by_scan(bundle: &ComputedShapesBundle, pos: Vector) -> Vec<usize> {
+        let shapes = (0..bundle.shapes.len()).rev();
+        shapes.filter(|&i| bundle.shapes[i].test(pos)).collect()
+    }
+
+    /// Shapes covering some of the pixel at `pos` through the grid & by a scan, topmost first,
+    /// if the grid has a single cell for it
+    fn covering(
+        bundle: &ComputedShapesBundle,
+        pos: Vector,
+        pixel_size: f32,
+    ) -> Option<[Vec<usize>; 2]> {
+        let covers = |&i: &usize| bundle.shapes[i].coverage(pos, pixel_size) > 0.;
+        let indices = bundle.grid.around(pos, pixel_size / 2.)?;
+
+        Some([
+            indices.iter().rev().copied().filter(covers).collect(),
+            (0..bundle.shapes.len()).rev().filter(covers).collect(),
+        ])
+    }
+
+    #[test]
+    fn grid_finds_the_shapes_a_full_scan_does() {
+        let sampler = IcnSampler::default();
+        let mut rng = StdRng::seed_from_u64(0x1c4);
+
+        for _ in 0..16 {
+            let icon: hir::IconHir = sampler.sample(&mut rng);
+            let (icon, _) = rotur_icn_resolver::resolve(&icon);
+            let bundle = ComputedShapesBundle::new(&icon);
+
+            // the canvas is moved partly or fully off the shapes, so edge & outer cells are hit
+            let camera = Vector {
+                x: rng.random_range(-25.0..25.0),
+                y: rng.random_range(-25.0..25.0),
+            };
+            let scaling: f32 = rng.random_range(0.5..4.0);
+            let pixel_size = 1. / scaling;
+
+            #[expect(clippy::cast_precision_loss)]
+            let pixels = (0..40).flat_map(|y| {
+                (0..40).map(move |x| {
+                    Vector {
+                        x: x as f32 - 20.,
+                        y: 20. - y as f32,
+                    } / scaling
+                        + camera
+                })
+            });
+
+            // with the corners of each shape's bounds, where a shape enters another cell
+            let corners = bundle.shapes.iter().flat_map(|sp| {
+                let (bl, tr) = sp.bb;
+                [
+                    bl,
+                    tr,
+                    Vector { x: bl.x, y: tr.y },
+                    Vector { x: tr.x, y: bl.y },
+                ]
+            });
+
+            for pos in pixels.chain(corners.collect::<Vec<_>>()) {
+                assert_eq!(
+                    hit_through_grid(&bundle, pos),
+                    hit_by_scan(&bundle, pos),
+                    "hits at {pos} differ\n{icon}"
+                );
+
+                if let Some([through_grid, by_scan]) = covering(&bundle, pos, pixel_size) {
+                    assert_eq!(through_grid, by_scan, "coverage at {pos} differs\n{icon}");
+                }
+            }
+        }
+    }
+}
@@ -10,6 +10,7 @@ pub struct Line {
     start: Vector,
     end: Vector,
     outline: f32,
+    halfwidth: f32,
 }
 
 impl Line {
@@ -19,23 +20,18 @@ impl Line {
             "equal points in the line should be resolved to a dot"
         );
 
-        let bb = crate::fitter::line::get_bounds(el);
+        let bb = el.bounds();
 
         Self {
             bb,
             start: el.start,
             end: el.end,
             outline: el.width.powi(2) / 4.,
+            halfwidth: el.width / 2.,
         }
     }
-}
-
-impl Shape for Line {
-    fn test(&self, pos: Vector) -> bool {
-        if !pos.within(self.bb) {
-            return false;
-        }
 
+    fn distance_sq(&self, pos: Vector) -> f32 {
         let ab = self.end - self.start;
 
         let ap = pos - self.start;
@@ -44,13 +40,25 @@ impl Shape for Line {
         let a_closer = ab_dot.is_sign_negative();
         let b_closer = ab_dot > ab.length_sq();
 
-        let d = match (a_closer, b_closer) {
+        match (a_closer, b_closer) {
             (true, false) => ap.length_sq(),
             (false, true) => (pos - self.end).length_sq(),
             (false, false) => ab.cross(ap).powi(2) / ab.length_sq(),
             (true, true) => unreachable!("point cannot be outside at both places"),
-        };
+        }
+    }
+}
+
+impl Shape for Line {
+    fn test(&self, pos: Vector) -> bool {
+        if !pos.within(self.bb) {
+            return false;
+        }
+
+        self.distance_sq(pos) <= self.outline
+    }
 
-        d <= self.outline
+    fn distance(&self, pos: Vector) -> f32 {
+        self.distance_sq(pos).sqrt() - self.halfwidth
     }
 }
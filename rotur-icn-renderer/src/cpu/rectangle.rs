@@ -41,4 +41,18 @@ impl Shape for Rectangle {
             (true, true) => d_vec.length_sq() <= self.outline.powi(2),
         }
     }
+
+    fn distance(&self, pos: Vector) -> f32 {
+        let d_vec = (pos - self.centre).abs() - self.halfsizes;
+
+        let outside = d_vec.max(Vector::ZERO).length();
+        let inside = d_vec.max_axis().min(0.);
+        let d = outside + inside;
+
+        if self.filled {
+            d - self.outline
+        } else {
+            d.abs() - self.outline
+        }
+    }
 }
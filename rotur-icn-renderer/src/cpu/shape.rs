@@ -1,18 +1,31 @@
 use rotur_icn_resolver::lir;
 use rotur_icn_units::Vector;
 
-use super::{arc, circle, colour::InternalColour, curve, disk, ellipse, line, rectangle, triangle};
+use super::{
+    arc, circle,
+    colour::{InternalColour, PremultipliedColour},
+    curve, disk, ellipse,
+    grid::ShapeGrid,
+    line, rectangle, triangle,
+};
 
 pub trait Shape {
     fn test(&self, pos: Vector) -> bool;
+
+    /// Signed distance from the shape's edge, negative inside
+    ///
+    /// Does no culling, unlike [`Shape::test`].
+    fn distance(&self, pos: Vector) -> f32;
 }
 
 pub struct ComputedShapesBundle {
     pub shapes: Vec<ComputedShape>,
+    pub grid: ShapeGrid,
 }
 
 pub struct ComputedShape {
     pub colour: InternalColour,
+    pub bb: (Vector, Vector),
     pub kind: ComputedShapeKind,
 }
 
@@ -29,22 +42,88 @@ pub enum ComputedShapeKind {
 
 impl ComputedShapesBundle {
     pub fn new(icon: &lir::IconLir) -> Self {
-        Self {
-            shapes: icon.elements.iter().map(ComputedShape::new).collect(),
+        let shapes: Vec<_> = icon.elements.iter().map(ComputedShape::new).collect();
+        let grid = ShapeGrid::new(&shapes.iter().map(|sp| sp.bb).collect::<Vec<_>>());
+
+        Self { shapes, grid }
+    }
+
+    /// Composite every shape hit exactly at `pos` over the background
+    pub fn colour_at(&self, pos: Vector, bg_colour: InternalColour) -> PremultipliedColour {
+        let shapes = self.grid.at(pos).iter().rev().map(|&i| &self.shapes[i]);
+
+        Self::composite(shapes, bg_colour, |sp| if sp.test(pos) { 1. } else { 0. })
+    }
+
+    /// Composite every shape over the background, weighted by its coverage of a pixel at `pos`
+    pub fn blended_colour_at(
+        &self,
+        pos: Vector,
+        pixel_size: f32,
+        bg_colour: InternalColour,
+    ) -> PremultipliedColour {
+        let coverage = |sp: &ComputedShape| sp.coverage(pos, pixel_size);
+
+        if let Some(indices) = self.grid.around(pos, pixel_size / 2.) {
+            let shapes = indices.iter().rev().map(|&i| &self.shapes[i]);
+            Self::composite(shapes, bg_colour, coverage)
+        } else {
+            Self::composite(self.shapes.iter().rev(), bg_colour, coverage)
+        }
+    }
+
+    // front-to-back, so that it can stop at the first fully opaque layer
+    fn composite<'sp>(
+        shapes: impl Iterator<Item = &'sp ComputedShape>,
+        bg_colour: InternalColour,
+        coverage: impl Fn(&ComputedShape) -> f32,
+    ) -> PremultipliedColour {
+        let mut acc = PremultipliedColour::default();
+
+        for sp in shapes {
+            let coverage = coverage(sp);
+            if coverage <= 0. {
+                continue;
+            }
+
+            acc.add_under(sp.colour, coverage);
+
+            if acc.is_opaque() {
+                return acc;
+            }
         }
+
+        acc.add_under(bg_colour, 1.);
+        acc
     }
 }
 
 impl ComputedShape {
     pub fn new(el: &lir::Element) -> Self {
+        let kind = ComputedShapeKind::new(&el.kind);
+
+        // disk's hit area is not the same as its geometric bounds
+        let bb = if let ComputedShapeKind::Disk(disk) = &kind {
+            disk.bounds()
+        } else {
+            el.bounds()
+        };
+
         Self {
             colour: el.colour.into(),
-            kind: ComputedShapeKind::new(&el.kind),
+            bb,
+            kind,
         }
     }
 
-    pub fn test_with_colour(&self, pos: Vector) -> Option<InternalColour> {
-        self.test(pos).then_some(self.colour)
+    /// Portion of a pixel centred at `pos` covered by the shape, in `0..=1`
+    pub fn coverage(&self, pos: Vector, pixel_size: f32) -> f32 {
+        let half = pixel_size / 2.;
+        if !pos.within((self.bb.0 - half, self.bb.1 + half)) {
+            return 0.;
+        }
+
+        (0.5 - self.distance(pos) / pixel_size).clamp(0., 1.)
     }
 }
 
@@ -75,6 +154,10 @@ impl Shape for ComputedShape {
     fn test(&self, pos: Vector) -> bool {
         self.kind.test(pos)
     }
+
+    fn distance(&self, pos: Vector) -> f32 {
+        self.kind.distance(pos)
+    }
 }
 
 impl Shape for ComputedShapeKind {
@@ -90,4 +173,118 @@ impl Shape for ComputedShapeKind {
             ComputedShapeKind::Curve(curve) => curve.test(pos),
         }
     }
+
+    fn distance(&self, pos: Vector) -> f32 {
+        match self {
+            ComputedShapeKind::Line(line) => line.distance(pos),
+            ComputedShapeKind::Disk(disk) => disk.distance(pos),
+            ComputedShapeKind::Circle(circle) => circle.distance(pos),
+            ComputedShapeKind::Rectangle(rectangle) => rectangle.distance(pos),
+            ComputedShapeKind::Triangle(triangle) => triangle.distance(pos),
+            ComputedShapeKind::Arc(arc) => arc.distance(pos),
+            ComputedShapeKind::Ellipse(ellipse) => ellipse.distance(pos),
+            ComputedShapeKind::Curve(curve) => curve.distance(pos),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng as _, SeedableRng as _, distr::Distribution as _, rngs::StdRng};
+    use rotur_icn_lowerer::hir;
+    use rotur_icn_rand::IcnSampler;
+    use rotur_icn_units::{Colour, Number, Vector};
+
+    use super::{ComputedShapesBundle, Shape as _};
+    use crate::cpu::Renderer;
+
+    const ICONS: usize = 16;
+
+    fn sample_icons() -> impl Iterator<Item = rotur_icn_resolver::lir::IconLir> {
+        let sampler = IcnSampler::default();
+        let mut rng = StdRng::seed_from_u64(0x1c4);
+
+        (0..ICONS).map(move |_| {
+            let icon: hir::IconHir = sampler.sample(&mut rng);
+            rotur_icn_resolver::resolve(&icon).0
+        })
+    }
+
+    #[test]
+    fn coverage_is_whole_away_from_edges_and_partial_on_them() {
+        let mut rng = StdRng::seed_from_u64(0x1c4);
+        let pixel_size = 0.25;
+
+        for icon in sample_icons() {
+            let bundle = ComputedShapesBundle::new(&icon);
+
+            for sp in &bundle.shapes {
+                let (bl, tr) = sp.bb;
+
+                for _ in 0..64 {
+                    let pos = Vector {
+                        x: rng.random_range(bl.x - 1.0..=tr.x + 1.0),
+                        y: rng.random_range(bl.y - 1.0..=tr.y + 1.0),
+                    };
+
+                    let distance = sp.distance(pos);
+                    let coverage = sp.coverage(pos, pixel_size);
+
+                    if distance.abs() >= pixel_size {
+                        let expected = if sp.test(pos) { 1. } else { 0. };
+                        assert!(
+                            (coverage - expected).abs() < Number::EPSILON,
+                            "{coverage} coverage at {pos}, {distance} away from the edge"
+                        );
+                    } else if distance.abs() < pixel_size / 4. && pos.within(sp.bb) {
+                        assert!(
+                            0. < coverage && coverage < 1.,
+                            "{coverage} coverage at {pos}, on the edge"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn aliased_render_is_the_topmost_hit_shape() {
+        let background = Colour {
+            r: 0x10,
+            g: 0x20,
+            b: 0x30,
+            a: 0xff,
+        };
+
+        for icon in sample_icons() {
+            let mut renderer = Renderer::new(Vector::new(20.), 3., Vector::ZERO, background);
+            renderer.load(&icon);
+
+            let (mut buf, (width, height)) = renderer.new_buf();
+            renderer.render(&mut buf);
+
+            let bundle = ComputedShapesBundle::new(&icon);
+
+            for (i, pixel) in buf.chunks_exact(4).enumerate() {
+                let (x, y) = (i % width, i / width);
+
+                #[expect(clippy::cast_precision_loss)]
+                let pos = Vector {
+                    x: x as Number - (width / 2) as Number,
+                    y: (height / 2) as Number - y as Number,
+                } / renderer.scaling;
+
+                // as it was drawn before compositing & anti-aliasing, for opaque colours
+                let expected = bundle
+                    .shapes
+                    .iter()
+                    .rev()
+                    .find(|sp| sp.test(pos))
+                    .map_or(background.into(), |sp| sp.colour)
+                    .to_bytes();
+
+                assert_eq!(pixel, expected, "pixel at {pos} differs\n{icon}");
+            }
+        }
+    }
 }
@@ -10,6 +10,7 @@ pub struct Triangle {
     c: Vector,
     normalisation: bool,
     outline: f32,
+    halfwidth: f32,
 }
 
 impl Triangle {
@@ -33,12 +34,13 @@ impl Triangle {
 
         Self {
             // TODO add internal culling box
-            bb: crate::fitter::triangle::get_bounds(el),
+            bb: el.bounds(),
             a: el.a,
             b: el.b,
             c: el.c,
             normalisation: (el.a - el.c).cross(el.b - el.a).is_sign_negative(),
             outline: el.outline_width.powi(2) / 4.,
+            halfwidth: el.outline_width / 2.,
         }
     }
 
@@ -56,14 +58,9 @@ impl Triangle {
             (true, true) => unreachable!("point cannot be outside at both places"),
         }
     }
-}
-
-impl Shape for Triangle {
-    fn test(&self, pos: Vector) -> bool {
-        if !pos.within(self.bb) {
-            return false;
-        }
 
+    /// Squared distance to the nearest side, or `None` if inside
+    fn outer_distance_sq(&self, pos: Vector) -> Option<f32> {
         let ab = self.b - self.a;
         let bc = self.c - self.b;
         let ca = self.a - self.c;
@@ -81,9 +78,9 @@ impl Shape for Triangle {
         let side_bc = bc_cro.is_sign_positive() ^ self.normalisation;
         let side_ca = ca_cro.is_sign_positive() ^ self.normalisation;
 
-        let d = match (side_ab, side_bc, side_ca) {
+        Some(match (side_ab, side_bc, side_ca) {
             // inside
-            (true, true, true) => return true,
+            (true, true, true) => return None,
             // opposite AB
             (false, true, true) => Self::line_dist(ab, ab_cro, ap, bp),
             // opposite B
@@ -99,8 +96,34 @@ impl Shape for Triangle {
             (false, false, false) => {
                 unreachable!("the point cannot be on the outside of every triangle's side")
             }
-        };
+        })
+    }
+
+    /// Distance to the nearest side from a point inside
+    fn inner_distance(&self, pos: Vector) -> f32 {
+        [(self.a, self.b), (self.b, self.c), (self.c, self.a)]
+            .into_iter()
+            .map(|(start, end)| {
+                let side = end - start;
+                side.cross(pos - start).abs() / side.length()
+            })
+            .fold(f32::INFINITY, f32::min)
+    }
+}
+
+impl Shape for Triangle {
+    fn test(&self, pos: Vector) -> bool {
+        if !pos.within(self.bb) {
+            return false;
+        }
+
+        self.outer_distance_sq(pos)
+            .is_none_or(|d| d <= self.outline)
+    }
 
-        d <= self.outline
+    fn distance(&self, pos: Vector) -> f32 {
+        self.outer_distance_sq(pos)
+            .map_or_else(|| -self.inner_distance(pos), f32::sqrt)
+            - self.halfwidth
     }
 }
@@ -1,2 +1,3 @@
 pub mod cpu;
 pub mod fitter;
+pub mod svg;
@@ -0,0 +1,154 @@
+use std::fmt::{self, Write as _};
+
+use rotur_icn_resolver::lir;
+use rotur_icn_units::{Colour, Number, Vector};
+
+mod arc;
+mod circle;
+mod curve;
+mod disk;
+mod ellipse;
+mod line;
+mod rectangle;
+mod triangle;
+
+pub struct Renderer {
+    pub background_colour: Colour,
+    pub canvas: Vector,
+    pub scaling: Number,
+    pub camera_pos: Vector,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            background_colour: Colour::ZERO,
+            canvas: Vector { x: 20., y: 20. },
+            scaling: 1.,
+            camera_pos: Vector::ZERO,
+        }
+    }
+}
+
+impl Renderer {
+    pub fn new(
+        canvas: Vector,
+        scaling: Number,
+        camera_pos: Vector,
+        background_colour: Colour,
+    ) -> Self {
+        Self {
+            background_colour,
+            canvas,
+            scaling,
+            camera_pos,
+        }
+    }
+
+    /// Render the ICN into an SVG document
+    ///
+    /// The view box is in ICN units, while the document's size is scaled.
+    pub fn render(&self, icon: &lir::IconLir) -> String {
+        let mut buf = String::new();
+
+        let size = (self.canvas * self.scaling).abs();
+        let top_left = Point(self.camera_pos + self.canvas.conj() / -2.);
+
+        writeln!(
+            buf,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{top_left} {} {}\">",
+            size.x,
+            size.y,
+            self.canvas.x.abs(),
+            self.canvas.y.abs(),
+        )
+        .unwrap();
+
+        if self.background_colour.a != 0 {
+            writeln!(
+                buf,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"{}/>",
+                top_left.0.x,
+                -top_left.0.y,
+                self.canvas.x.abs(),
+                self.canvas.y.abs(),
+                Paint::Fill(self.background_colour),
+            )
+            .unwrap();
+        }
+
+        for el in &icon.elements {
+            match &el.kind {
+                lir::ElementKind::Line(line) => line::write(&mut buf, line, el.colour),
+                lir::ElementKind::Disk(disk) => disk::write(&mut buf, disk, el.colour),
+                lir::ElementKind::Circle(circle) => circle::write(&mut buf, circle, el.colour),
+                lir::ElementKind::Rectangle(rectangle) => {
+                    rectangle::write(&mut buf, rectangle, el.colour);
+                }
+                lir::ElementKind::Triangle(triangle) => {
+                    triangle::write(&mut buf, triangle, el.colour);
+                }
+                lir::ElementKind::Arc(arc) => arc::write(&mut buf, arc, el.colour),
+                lir::ElementKind::Ellipse(ellipse) => ellipse::write(&mut buf, ellipse, el.colour),
+                lir::ElementKind::Curve(curve) => curve::write(&mut buf, curve, el.colour),
+            }
+
+            buf.push('\n');
+        }
+
+        buf.push_str("</svg>\n");
+
+        buf
+    }
+}
+
+/// A point in SVG space, where the Y axis points down
+struct Point(Vector);
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.0.x, -self.0.y)
+    }
+}
+
+/// Fill or stroke attributes of an element
+enum Paint {
+    Fill(Colour),
+    Stroke(Colour, Number),
+    FillStroke(Colour, Number),
+}
+
+impl fmt::Display for Paint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (colour, fill, stroke_width) = match *self {
+            Self::Fill(colour) => (colour, true, None),
+            Self::Stroke(colour, width) => (colour, false, Some(width)),
+            Self::FillStroke(colour, width) => (colour, true, Some(width)),
+        };
+
+        let opacity = (colour.a != 0xff).then(|| f32::from(colour.a) / 255.);
+
+        if fill {
+            write!(f, " fill=\"{colour}\"")?;
+
+            if let Some(opacity) = opacity {
+                write!(f, " fill-opacity=\"{opacity}\"")?;
+            }
+        } else {
+            write!(f, " fill=\"none\"")?;
+        }
+
+        if let Some(width) = stroke_width {
+            write!(
+                f,
+                " stroke=\"{colour}\" stroke-width=\"{width}\" stroke-linecap=\"round\" stroke-linejoin=\"round\""
+            )?;
+
+            if let Some(opacity) = opacity {
+                write!(f, " stroke-opacity=\"{opacity}\"")?;
+            }
+        }
+
+        Ok(())
+    }
+}
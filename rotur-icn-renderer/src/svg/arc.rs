@@ -0,0 +1,25 @@
+use std::fmt::Write as _;
+
+use rotur_icn_resolver::lir;
+use rotur_icn_units::{Colour, Vector};
+
+use super::{Paint, Point};
+
+pub fn write(buf: &mut String, el: &lir::Arc, colour: Colour) {
+    let start = el.centre + Vector::new_from_length(el.radius, el.start_angle);
+    let end = el.centre + Vector::new_from_length(el.radius, el.end_angle);
+
+    let large_arc = el.end_angle - el.start_angle > std::f32::consts::PI;
+
+    // the arc always goes counter-clockwise, which stays so in SVG space, hence sweep is unset
+    write!(
+        buf,
+        "<path d=\"M {} A {r} {r} 0 {} 0 {}\"{}/>",
+        Point(start),
+        u8::from(large_arc),
+        Point(end),
+        Paint::Stroke(colour, el.width),
+        r = el.radius,
+    )
+    .unwrap();
+}
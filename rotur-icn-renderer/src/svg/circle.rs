@@ -0,0 +1,18 @@
+use std::fmt::Write as _;
+
+use rotur_icn_resolver::lir;
+use rotur_icn_units::Colour;
+
+use super::Paint;
+
+pub fn write(buf: &mut String, el: &lir::Circle, colour: Colour) {
+    write!(
+        buf,
+        "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"{}/>",
+        el.centre.x,
+        -el.centre.y,
+        el.radius,
+        Paint::Stroke(colour, el.width),
+    )
+    .unwrap();
+}
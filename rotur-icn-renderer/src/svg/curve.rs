@@ -0,0 +1,18 @@
+use std::fmt::Write as _;
+
+use rotur_icn_resolver::lir;
+use rotur_icn_units::Colour;
+
+use super::{Paint, Point};
+
+pub fn write(buf: &mut String, el: &lir::Curve, colour: Colour) {
+    write!(
+        buf,
+        "<path d=\"M {} Q {} {}\"{}/>",
+        Point(el.start),
+        Point(el.control),
+        Point(el.end),
+        Paint::Stroke(colour, el.width),
+    )
+    .unwrap();
+}
@@ -0,0 +1,19 @@
+use std::fmt::Write as _;
+
+use rotur_icn_resolver::lir;
+use rotur_icn_units::Colour;
+
+use super::Paint;
+
+pub fn write(buf: &mut String, el: &lir::Disk, colour: Colour) {
+    write!(
+        buf,
+        "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"{}/>",
+        el.centre.x,
+        -el.centre.y,
+        // the raster compares the squared distance against the radius, see `cpu::Disk`
+        el.radius.sqrt(),
+        Paint::Fill(colour),
+    )
+    .unwrap();
+}
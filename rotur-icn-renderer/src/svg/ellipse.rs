@@ -0,0 +1,21 @@
+use std::fmt::Write as _;
+
+use rotur_icn_resolver::lir;
+use rotur_icn_units::Colour;
+
+use super::Paint;
+
+pub fn write(buf: &mut String, el: &lir::Ellipse, colour: Colour) {
+    let cx = el.centre.x;
+    let cy = -el.centre.y;
+
+    write!(
+        buf,
+        "<ellipse cx=\"{cx}\" cy=\"{cy}\" rx=\"{}\" ry=\"{}\" transform=\"rotate({} {cx} {cy})\"{}/>",
+        el.axis.x,
+        el.axis.y,
+        -el.direction.to_degrees(),
+        Paint::Stroke(colour, el.outline_width),
+    )
+    .unwrap();
+}
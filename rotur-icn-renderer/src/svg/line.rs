@@ -0,0 +1,17 @@
+use std::fmt::Write as _;
+
+use rotur_icn_resolver::lir;
+use rotur_icn_units::Colour;
+
+use super::{Paint, Point};
+
+pub fn write(buf: &mut String, el: &lir::Line, colour: Colour) {
+    write!(
+        buf,
+        "<path d=\"M {} L {}\"{}/>",
+        Point(el.start),
+        Point(el.end),
+        Paint::Stroke(colour, el.width),
+    )
+    .unwrap();
+}
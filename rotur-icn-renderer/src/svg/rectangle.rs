@@ -0,0 +1,24 @@
+use std::fmt::Write as _;
+
+use rotur_icn_resolver::lir;
+use rotur_icn_units::Colour;
+
+use super::Paint;
+
+pub fn write(buf: &mut String, el: &lir::Rectangle, colour: Colour) {
+    let paint = if el.filled {
+        Paint::FillStroke(colour, el.outline_width)
+    } else {
+        Paint::Stroke(colour, el.outline_width)
+    };
+
+    write!(
+        buf,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"{paint}/>",
+        el.bottom_left.x,
+        -(el.bottom_left.y + el.sizes.y),
+        el.sizes.x,
+        el.sizes.y,
+    )
+    .unwrap();
+}
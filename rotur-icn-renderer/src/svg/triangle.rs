@@ -0,0 +1,18 @@
+use std::fmt::Write as _;
+
+use rotur_icn_resolver::lir;
+use rotur_icn_units::Colour;
+
+use super::{Paint, Point};
+
+pub fn write(buf: &mut String, el: &lir::Triangle, colour: Colour) {
+    write!(
+        buf,
+        "<path d=\"M {} L {} L {} Z\"{}/>",
+        Point(el.a),
+        Point(el.b),
+        Point(el.c),
+        Paint::FillStroke(colour, el.outline_width),
+    )
+    .unwrap();
+}
@@ -0,0 +1,98 @@
+use rotur_icn_renderer::cpu::{AlphaMode, Renderer};
+use rotur_icn_resolver::lir;
+use rotur_icn_units::{Colour, Vector};
+
+const HALF_RED: Colour = Colour {
+    r: 0xff,
+    g: 0,
+    b: 0,
+    a: 0x80,
+};
+const HALF_GREEN: Colour = Colour {
+    r: 0,
+    g: 0xff,
+    b: 0,
+    a: 0x80,
+};
+const HALF_BLUE: Colour = Colour {
+    r: 0,
+    g: 0,
+    b: 0xff,
+    a: 0x80,
+};
+
+/// The only pixel of a canvas covered by the disks of the colours, drawn bottom first
+fn pixel(colours: &[Colour], background: Colour, alpha_mode: AlphaMode) -> [u8; 4] {
+    let icon = lir::IconLir {
+        elements: colours
+            .iter()
+            .map(|&colour| lir::Element {
+                colour,
+                kind: lir::ElementKind::Disk(lir::Disk {
+                    centre: Vector::ZERO,
+                    radius: 1.,
+                }),
+            })
+            .collect(),
+    };
+
+    let mut renderer = Renderer::new(Vector::new(1.), 1., Vector::ZERO, background);
+    renderer.alpha_mode = alpha_mode;
+    renderer.load(&icon);
+
+    let (mut buf, size) = renderer.new_buf();
+    renderer.render(&mut buf);
+
+    assert_eq!(size, (1, 1));
+    buf.try_into().expect("the canvas has a single pixel")
+}
+
+#[test]
+fn translucent_shapes_composite_over_each_other() {
+    // half of the red, then half of the rest of the blue
+    assert_eq!(
+        pixel(&[HALF_BLUE, HALF_RED], Colour::ZERO, AlphaMode::Straight),
+        [170, 0, 85, 192]
+    );
+}
+
+#[test]
+fn translucent_shapes_composite_over_a_translucent_background() {
+    assert_eq!(
+        pixel(&[HALF_RED], HALF_GREEN, AlphaMode::Straight),
+        [170, 85, 0, 192]
+    );
+}
+
+#[test]
+fn opaque_shapes_hide_whatever_is_beneath() {
+    let red = Colour {
+        a: 0xff,
+        ..HALF_RED
+    };
+
+    assert_eq!(
+        pixel(&[HALF_BLUE, red], HALF_GREEN, AlphaMode::Straight),
+        [255, 0, 0, 255]
+    );
+}
+
+#[test]
+fn premultiplied_pixels_are_the_straight_ones_scaled_by_alpha() {
+    let straight = pixel(&[HALF_BLUE, HALF_RED], Colour::ZERO, AlphaMode::Straight);
+    let premultiplied = pixel(
+        &[HALF_BLUE, HALF_RED],
+        Colour::ZERO,
+        AlphaMode::Premultiplied,
+    );
+
+    assert_eq!(premultiplied, [128, 0, 64, 192]);
+    assert_eq!(straight[3], premultiplied[3]);
+
+    let alpha = u16::from(straight[3]);
+
+    for (straight, premultiplied) in straight[..3].iter().zip(&premultiplied[..3]) {
+        let scaled = u16::from(*straight) * alpha / 255;
+        assert!(scaled.abs_diff(u16::from(*premultiplied)) <= 1);
+    }
+}
@@ -0,0 +1,127 @@
+use rotur_icn_renderer::cpu::{RenderError, Renderer};
+use rotur_icn_resolver::lir;
+use rotur_icn_units::{Colour, Number, Vector};
+
+const CANVAS: Vector = Vector { x: 4., y: 2. };
+
+/// Sets a parameter of the renderer to a non-finite value
+type Unfinite = fn(&mut Renderer);
+
+/// A renderer of a 8×4 canvas, with a disk loaded
+fn loaded_renderer() -> Renderer {
+    let mut renderer = Renderer::new(CANVAS, 2., Vector::ZERO, Colour::ZERO);
+
+    let icon = "Icon elements (1 total):\n- disk (0, 0) r1 col#ffffff\n"
+        .parse::<lir::IconLir>()
+        .expect("the LIR should read");
+    renderer.load(&icon);
+
+    renderer
+}
+
+#[test]
+fn checked_render_matches_the_unchecked_one() {
+    let mut renderer = loaded_renderer();
+
+    let (mut checked, size) = renderer.try_new_buf().expect("the canvas is valid");
+    renderer
+        .try_render(&mut checked)
+        .expect("the render is valid");
+
+    let (mut unchecked, _) = renderer.new_buf();
+    renderer.render(&mut unchecked);
+
+    assert_eq!(size, (8, 4));
+    assert_eq!(checked, unchecked);
+}
+
+#[test]
+fn rendering_without_an_icon_fails() {
+    let mut renderer = Renderer::new(CANVAS, 2., Vector::ZERO, Colour::ZERO);
+
+    let (mut buf, _) = renderer.try_new_buf().expect("the canvas is valid");
+
+    assert_eq!(
+        renderer.try_render(&mut buf),
+        Err(RenderError::NoIconLoaded)
+    );
+}
+
+#[test]
+fn rendering_into_a_wrong_buffer_fails() {
+    let mut renderer = loaded_renderer();
+
+    for len in [0, 8 * 4 * 4 - 1, 8 * 4 * 4 + 4] {
+        let mut buf = vec![0; len];
+
+        assert_eq!(
+            renderer.try_render(&mut buf),
+            Err(RenderError::BadBufferSize {
+                expected: 8 * 4 * 4,
+                found: len,
+            })
+        );
+    }
+}
+
+#[test]
+fn canvas_over_the_budget_fails() {
+    let mut renderer = loaded_renderer();
+    renderer.pixel_budget = 31;
+
+    let too_large = Err(RenderError::CanvasTooLarge {
+        width: 8,
+        height: 4,
+        budget: 31,
+    });
+
+    assert_eq!(renderer.try_scaled_buf_size(), too_large);
+    assert_eq!(renderer.try_new_buf().map(|(_, size)| size), too_large);
+    assert_eq!(
+        renderer.try_render(&mut [0; 8 * 4 * 4]),
+        too_large.map(|_| ())
+    );
+
+    renderer.pixel_budget = 32;
+    assert_eq!(renderer.try_scaled_buf_size(), Ok((8, 4)));
+}
+
+#[test]
+fn canvas_too_large_for_memory_fails_whatever_the_budget() {
+    let mut renderer = loaded_renderer();
+    renderer.pixel_budget = usize::MAX;
+    renderer.scaling = 1e30;
+
+    assert!(matches!(
+        renderer.try_new_buf(),
+        Err(RenderError::CanvasTooLarge { .. })
+    ));
+}
+
+#[test]
+fn non_finite_parameters_fail() {
+    let cases: [(&str, Unfinite); 5] = [
+        ("canvas width", |renderer| renderer.canvas.x = Number::NAN),
+        ("canvas height", |renderer| {
+            renderer.canvas.y = Number::INFINITY;
+        }),
+        ("scaling", |renderer| renderer.scaling = Number::NAN),
+        ("camera x", |renderer| {
+            renderer.camera_pos.x = Number::NEG_INFINITY;
+        }),
+        ("camera y", |renderer| renderer.camera_pos.y = Number::NAN),
+    ];
+
+    for (name, unfinite) in cases {
+        let mut renderer = loaded_renderer();
+        unfinite(&mut renderer);
+
+        let non_finite = Err(RenderError::NonFiniteParameter(name));
+
+        assert_eq!(renderer.try_scaled_buf_size(), non_finite);
+        assert_eq!(
+            renderer.try_render(&mut [0; 8 * 4 * 4]),
+            non_finite.map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt::Write as _;
+
+use rotur_icn_renderer::{cpu, svg};
+use rotur_icn_resolver::lir;
+use rotur_icn_units::{Colour, Number, Vector};
+
+const CANVAS: Vector = Vector { x: 20., y: 20. };
+/// Pixels per unit of the raster, which the extents are measured at
+const SCALE: Number = 4.;
+
+fn icon(elements: &[&str]) -> lir::IconLir {
+    let mut src = format!("Icon elements ({} total):\n", elements.len());
+
+    for el in elements {
+        writeln!(src, "- {el} col#ffffff").expect("writing to a string doesn't fail");
+    }
+
+    src.parse().expect("the LIR should read")
+}
+
+/// The element of the SVG document, after the background
+fn svg_element(icon: &lir::IconLir) -> String {
+    let svg = svg::Renderer::default().render(icon);
+
+    svg.lines()
+        .nth(1)
+        .expect("the SVG has the element")
+        .to_owned()
+}
+
+fn attr<'a>(element: &'a str, name: &str) -> &'a str {
+    let start = element
+        .find(&format!(" {name}=\""))
+        .unwrap_or_else(|| panic!("{element} has no {name}"))
+        + name.len()
+        + 3;
+    let len = element[start..].find('"').expect("the value is quoted");
+
+    &element[start..start + len]
+}
+
+fn number_attr(element: &str, name: &str) -> Number {
+    attr(element, name).parse().expect("the value is a number")
+}
+
+fn assert_close(actual: Number, expected: Number, tolerance: Number) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{actual} isn't within {tolerance} of {expected}"
+    );
+}
+
+/// The raster of the ICN without anti-aliasing, centred on the origin
+fn raster(icon: &lir::IconLir) -> (Vec<u8>, (usize, usize)) {
+    let mut renderer = cpu::Renderer::new(CANVAS, SCALE, Vector::ZERO, Colour::ZERO);
+    renderer.load(icon);
+
+    let (mut buf, size) = renderer.new_buf();
+    renderer.render(&mut buf);
+
+    (buf, size)
+}
+
+/// Width & height in units, from the first to the last pixel drawn in the middle row & column of the raster
+#[expect(clippy::cast_precision_loss)]
+fn raster_extent(icon: &lir::IconLir) -> Vector {
+    let (buf, (width, height)) = raster(icon);
+    let covered = |x: usize, y: usize| buf[(y * width + x) * 4 + 3] != 0;
+
+    let span = |covered: Vec<usize>| match covered.as_slice() {
+        [first, .., last] => (last - first + 1) as Number,
+        _ => covered.len() as Number,
+    };
+
+    let columns = (0..width).filter(|&x| covered(x, height / 2)).collect();
+    let rows = (0..height).filter(|&y| covered(width / 2, y)).collect();
+
+    Vector {
+        x: span(columns),
+        y: span(rows),
+    } / SCALE
+}
+
+/// Whether the raster covers the point, in ICN units
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn raster_covers(icon: &lir::IconLir, pos: Vector) -> bool {
+    let (buf, (width, height)) = raster(icon);
+
+    let x = (pos.x * SCALE + (width / 2) as Number).round() as usize;
+    let y = (-pos.y * SCALE + (height / 2) as Number).round() as usize;
+
+    buf[(y * width + x) * 4 + 3] != 0
+}
+
+#[test]
+fn view_box_is_centred_on_the_flipped_camera() {
+    let renderer = svg::Renderer::new(
+        Vector { x: 20., y: 10. },
+        2.,
+        Vector { x: 3., y: 4. },
+        Colour {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        },
+    );
+
+    let svg = renderer.render(&icon(&["disk (3, 4) r1"]));
+    let mut lines = svg.lines();
+
+    assert_eq!(
+        lines.next(),
+        Some(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"40\" height=\"20\" viewBox=\"-7 -9 20 10\">"
+        )
+    );
+
+    let background = lines.next().expect("the background is drawn");
+    assert_eq!(
+        [
+            attr(background, "x"),
+            attr(background, "y"),
+            attr(background, "width"),
+            attr(background, "height"),
+        ],
+        ["-7", "-9", "20", "10"]
+    );
+
+    let disk = lines.next().expect("the disk is drawn");
+    assert_eq!([attr(disk, "cx"), attr(disk, "cy")], ["3", "-4"]);
+}
+
+#[test]
+fn transparent_background_isnt_drawn() {
+    let svg = svg::Renderer::default().render(&icon(&[]));
+
+    assert_eq!(svg.lines().count(), 2);
+}
+
+/// Flags & end point of the arc's path, in SVG space
+fn arc_path(element: &str) -> ([u8; 2], Vector) {
+    let d = attr(element, "d").split_whitespace().collect::<Vec<_>>();
+    assert_eq!((d[0], d[3]), ("M", "A"));
+
+    let number = |i: usize| d[i].parse::<Number>().expect("the path has numbers");
+    let flag = |i: usize| d[i].parse::<u8>().expect("the flags are 0 or 1");
+
+    (
+        [flag(7), flag(8)],
+        Vector {
+            x: number(9),
+            y: number(10),
+        },
+    )
+}
+
+#[test]
+fn arcs_sweep_counter_clockwise() {
+    let quarter = icon(&["arc (0, 0) r4 w1 0rad - 1.5707964rad"]);
+    let (flags, end) = arc_path(&svg_element(&quarter));
+
+    // small, with the sweep flag unset, which is counter-clockwise with the Y axis pointing down
+    assert_eq!(flags, [0, 0]);
+    assert_close(end.x, 0., 1e-4);
+    assert_close(end.y, -4., 1e-4);
+
+    // so it covers the top right quarter, as the raster does
+    assert!(raster_covers(&quarter, Vector::new_from_length(4., 0.8)));
+    assert!(!raster_covers(&quarter, Vector::new_from_length(4., -0.8)));
+
+    let three_quarters = icon(&["arc (0, 0) r4 w1 0rad - 4.712389rad"]);
+    let (flags, end) = arc_path(&svg_element(&three_quarters));
+
+    assert_eq!(flags, [1, 0]);
+    assert_close(end.x, 0., 1e-4);
+    assert_close(end.y, 4., 1e-4);
+
+    assert!(raster_covers(
+        &three_quarters,
+        Vector::new_from_length(4., 3.)
+    ));
+    assert!(!raster_covers(
+        &three_quarters,
+        Vector::new_from_length(4., -0.8)
+    ));
+}
+
+#[test]
+fn disks_are_as_large_as_in_the_raster() {
+    let disk = icon(&["disk (0, 0) r16"]);
+    let element = svg_element(&disk);
+
+    let radius = number_attr(&element, "r");
+    assert_close(radius, 4., 1e-6);
+
+    let extent = raster_extent(&disk);
+    assert_close(extent.x, radius * 2., 1. / SCALE);
+    assert_close(extent.y, radius * 2., 1. / SCALE);
+}
+
+#[test]
+fn rectangles_are_placed_by_their_top_left_corner_with_a_centred_outline() {
+    let filled = icon(&["rectangle (-4, -3) s(8, 4) filled ow2"]);
+    let element = svg_element(&filled);
+
+    assert_eq!(
+        [
+            attr(&element, "x"),
+            attr(&element, "y"),
+            attr(&element, "width"),
+            attr(&element, "height"),
+            attr(&element, "stroke-width"),
+        ],
+        ["-4", "-1", "8", "4", "2"]
+    );
+    assert_ne!(attr(&element, "fill"), "none");
+
+    // the outline is centred on the edges in both
+    let extent = raster_extent(&icon(&["rectangle (-4, -2) s(8, 4) filled ow2"]));
+    assert_close(extent.x, 8. + 2., 1. / SCALE);
+    assert_close(extent.y, 4. + 2., 1. / SCALE);
+
+    let hollow = icon(&["rectangle (-4, -2) s(8, 4) hollow ow2"]);
+    assert_eq!(attr(&svg_element(&hollow), "fill"), "none");
+    assert!(!raster_covers(&hollow, Vector::ZERO));
+}
+
+#[test]
+fn ellipses_are_rotated_as_in_the_raster() {
+    let ellipse = icon(&["ellipse (0, 0) s(6, 2) dir 1.5707964rad ow1"]);
+    let element = svg_element(&ellipse);
+
+    let axis = Vector {
+        x: number_attr(&element, "rx"),
+        y: number_attr(&element, "ry"),
+    };
+    assert_eq!(axis, Vector { x: 6., y: 2. });
+
+    // rotating by a negative angle with the Y axis pointing down turns the major axis upwards
+    let rotation = attr(&element, "transform");
+    let angle = rotation
+        .strip_prefix("rotate(")
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|angle| angle.parse::<Number>().ok())
+        .expect("the ellipse is rotated");
+    assert_close(angle, -90., 1e-4);
+
+    // so the major axis is vertical in the raster too
+    let extent = raster_extent(&ellipse);
+    assert_close(extent.x, (axis.y + 0.5) * 2., 1. / SCALE);
+    assert_close(extent.y, (axis.x + 0.5) * 2., 1. / SCALE);
+}
@@ -0,0 +1,132 @@
+use rotur_icn_units::Vector;
+
+use super::lir;
+
+impl lir::Element {
+    /// Bottom-left & top-right corners of the box the element is drawn within
+    pub fn bounds(&self) -> (Vector, Vector) {
+        match &self.kind {
+            lir::ElementKind::Line(line) => line.bounds(),
+            lir::ElementKind::Disk(disk) => disk.bounds(),
+            lir::ElementKind::Circle(circle) => circle.bounds(),
+            lir::ElementKind::Rectangle(rectangle) => rectangle.bounds(),
+            lir::ElementKind::Triangle(triangle) => triangle.bounds(),
+            lir::ElementKind::Arc(arc) => arc.bounds(),
+            lir::ElementKind::Ellipse(ellipse) => ellipse.bounds(),
+            lir::ElementKind::Curve(curve) => curve.bounds(),
+        }
+    }
+}
+
+impl lir::Line {
+    pub fn bounds(&self) -> (Vector, Vector) {
+        let (bl, tr) = points_bounds([self.start, self.end].into_iter());
+        let pad = self.width / 2.;
+        (bl - pad, tr + pad)
+    }
+}
+
+impl lir::Disk {
+    pub fn bounds(&self) -> (Vector, Vector) {
+        (self.centre - self.radius, self.centre + self.radius)
+    }
+}
+
+impl lir::Circle {
+    pub fn bounds(&self) -> (Vector, Vector) {
+        (
+            self.centre - self.radius - self.width / 2.,
+            self.centre + self.radius + self.width / 2.,
+        )
+    }
+}
+
+impl lir::Rectangle {
+    pub fn bounds(&self) -> (Vector, Vector) {
+        let pad = self.outline_width / 2.;
+        (self.bottom_left - pad, self.bottom_left + self.sizes + pad)
+    }
+}
+
+impl lir::Triangle {
+    pub fn bounds(&self) -> (Vector, Vector) {
+        let (bl, tr) = points_bounds([self.a, self.b, self.c].into_iter());
+        let pad = self.outline_width / 2.;
+        (bl - pad, tr + pad)
+    }
+}
+
+impl lir::Arc {
+    pub fn bounds(&self) -> (Vector, Vector) {
+        let start = Vector::new_from_length(self.radius, self.start_angle);
+        let end = Vector::new_from_length(self.radius, self.end_angle);
+        let bounds = points_bounds([start, end].into_iter());
+
+        let (bl, tr) = [
+            0.,
+            std::f32::consts::FRAC_PI_2,
+            std::f32::consts::PI,
+            std::f32::consts::PI + std::f32::consts::FRAC_PI_2,
+        ]
+        .into_iter()
+        .flat_map(|a| [a, -a].into_iter()) // FIXME a very lazy solution
+        .filter(|&t| self.start_angle < t && t < self.end_angle)
+        .map(|t| Vector::new_from_length(self.radius, t))
+        .fold(bounds, extend_bound);
+
+        let pad = self.width / 2.;
+        (self.centre + bl - pad, self.centre + tr + pad)
+    }
+}
+
+impl lir::Ellipse {
+    pub fn bounds(&self) -> (Vector, Vector) {
+        let pad = self.outline_width / 2.;
+
+        let axis2 = self.axis.powi(2);
+        let phase = axis2.x - axis2.y;
+        let coef = self.direction.sin().powi(2);
+        let offset = phase * coef;
+
+        let bounds = Vector {
+            x: axis2.x - offset,
+            y: axis2.y + offset,
+        }
+        .sqrt();
+
+        (self.centre - bounds - pad, self.centre + bounds + pad)
+    }
+}
+
+impl lir::Curve {
+    pub fn bounds(&self) -> (Vector, Vector) {
+        let ba = self.start - self.control;
+        let bc = self.end - self.control;
+        let alpha = ba + bc;
+        let beta = -2. * ba;
+
+        let bounds = points_bounds([self.start, self.end].into_iter());
+
+        let q = ba / alpha;
+
+        let (bl, tr) = [q.x, q.y]
+            .into_iter()
+            .filter(|&t| 0. < t && t < 1.)
+            .map(|t| self.start + t * (beta + alpha * t))
+            .fold(bounds, extend_bound);
+
+        let pad = self.width / 2.;
+
+        (bl - pad, tr + pad)
+    }
+}
+
+fn points_bounds(mut p: impl Iterator<Item = Vector>) -> (Vector, Vector) {
+    let base = p.next().expect("there should be at least one point");
+
+    p.fold((base, base), extend_bound)
+}
+
+fn extend_bound(b: (Vector, Vector), p: Vector) -> (Vector, Vector) {
+    (b.0.min(p), b.1.max(p))
+}
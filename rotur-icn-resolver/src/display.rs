@@ -84,7 +84,7 @@ impl fmt::Display for lir::Arc {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "arc {} r{} w{} {:.2}rad - {:.2}rad",
+            "arc {} r{} w{} {}rad - {}rad",
             self.centre, self.radius, self.width, self.start_angle, self.end_angle,
         )
     }
@@ -94,7 +94,7 @@ impl fmt::Display for lir::Ellipse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "ellipse {} s{} dir {:.2}rad ow{}",
+            "ellipse {} s{} dir {}rad ow{}",
             self.centre, self.axis, self.direction, self.outline_width,
         )
     }
@@ -1,13 +1,15 @@
 use std::fmt;
 
-use rotur_icn_lexer::{display::PosDisplay, token};
+use rotur_icn_lexer::{Suggestion, display::PosDisplay, token};
 
+#[derive(Debug, Clone)]
 pub struct Error {
     pub cmd_pos: token::Pos,
     pub cmd_index: usize,
     pub kind: ErrorKind,
 }
 
+#[derive(Debug, Clone)]
 pub enum ErrorKind {
     DanglingContinuedLine,
 }
@@ -37,6 +39,16 @@ impl fmt::Display for ErrorKind {
     }
 }
 
+impl Error {
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        match self.kind {
+            // where the line should start from is up to the author,
+            // replacing it with a dot would change what's drawn
+            ErrorKind::DanglingContinuedLine => None,
+        }
+    }
+}
+
 impl ErrorKind {
     pub fn code(&self) -> &'static str {
         match self {
@@ -1,194 +1,258 @@
-use rotur_icn_units::{Colour, Vector};
+use rotur_icn_units::{Colour, Number, Vector};
 
+pub use rotur_icn_lowerer::Compat;
 use rotur_icn_lowerer::hir;
 
+mod bounds;
 mod display;
 mod error;
 pub mod lir;
+mod read;
 
 pub use error::{Error, ErrorKind};
+pub use read::{ReadError, ReadErrorKind};
 
 pub fn resolve(hir: &hir::IconHir) -> (lir::IconLir, Vec<Error>) {
+    resolve_with(hir, Compat::default())
+}
+
+pub fn resolve_with(hir: &hir::IconHir, compat: Compat) -> (lir::IconLir, Vec<Error>) {
     let mut errors = Vec::new();
 
-    let mut origin = Vector::ZERO;
-    let mut colour = Colour::WHITE;
-    let mut width = 5.;
-    let mut last_point = None;
+    let mut resolver = Resolver::new(compat);
+
+    let elements = hir
+        .operations
+        .iter()
+        .enumerate()
+        .filter_map(|(op_i, op)| resolver.step(&mut errors, op_i, op))
+        .collect();
+
+    (lir::IconLir { elements }, errors)
+}
+
+/// State carried between operations while resolving
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    pub origin: Vector,
+    pub colour: Colour,
+    pub width: Number,
+    /// Where a continued line would start from
+    pub last_point: Option<Vector>,
+    dangling_contlines_chained: bool,
+    compat: Compat,
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new(Compat::default())
+    }
+}
+
+impl Resolver {
+    pub fn new(compat: Compat) -> Self {
+        Self {
+            origin: Vector::ZERO,
+            colour: Colour::WHITE,
+            width: compat.default_width,
+            last_point: None,
+            dangling_contlines_chained: false,
+            compat,
+        }
+    }
+
+    pub fn compat(&self) -> Compat {
+        self.compat
+    }
 
-    let mut elements = Vec::with_capacity(hir.operations.len());
-    let mut dangling_contlines_chained = false;
-    for (op_i, op) in hir.operations.iter().enumerate() {
+    /// Apply a single operation, returning the element it draws, if any
+    #[expect(
+        clippy::too_many_lines,
+        reason = "a flat match over every operation kind"
+    )]
+    pub fn step(
+        &mut self,
+        errors: &mut Vec<Error>,
+        op_i: usize,
+        op: &hir::Operation,
+    ) -> Option<lir::Element> {
         if !matches!(op.kind, hir::OperationKind::ContinueLine(..)) {
-            dangling_contlines_chained = false;
+            self.dangling_contlines_chained = false;
         }
 
         let el = lir::Element {
-            colour,
+            colour: self.colour,
             kind: match &op.kind {
                 hir::OperationKind::SetWidth(set_width) => {
-                    width = set_width.value;
-                    continue;
+                    self.width = set_width.value;
+                    return None;
                 }
                 hir::OperationKind::SetColour(set_colour) => {
-                    colour = set_colour.value;
-                    continue;
+                    self.colour = set_colour.value;
+                    return None;
                 }
                 hir::OperationKind::DrawLine(draw_line) => {
-                    let end = origin + draw_line.end;
+                    let end = self.origin + draw_line.end;
 
-                    last_point = Some(end);
+                    self.last_point = Some(end);
 
                     if draw_line.start == draw_line.end {
                         lir::ElementKind::Disk(lir::Disk {
                             centre: end,
-                            radius: width / 2.,
+                            radius: self.width / 2.,
                         })
                     } else {
                         lir::ElementKind::Line(lir::Line {
-                            start: origin + draw_line.start,
+                            start: self.origin + draw_line.start,
                             end,
-                            width,
+                            width: self.width,
                         })
                     }
                 }
                 hir::OperationKind::ContinueLine(continue_line) => {
-                    if let Some(start) = last_point {
+                    if let Some(last_point) = self.last_point {
                         debug_assert!(
-                            !dangling_contlines_chained,
+                            !self.dangling_contlines_chained,
                             "this shouldn't happen as dangling continued lines don't define a last point",
                         );
 
-                        let end = origin + continue_line.next;
+                        let end = self.origin + continue_line.next;
+
+                        self.last_point = Some(end);
 
-                        last_point = Some(end);
+                        let (start, collapsed) = if self.compat.offset_continued_lines {
+                            (self.origin + last_point, last_point == continue_line.next)
+                        } else {
+                            (last_point, last_point == end)
+                        };
 
-                        if start == continue_line.next {
+                        if collapsed {
                             lir::ElementKind::Disk(lir::Disk {
                                 centre: end,
-                                radius: width / 2.,
+                                radius: self.width / 2.,
                             })
                         } else {
                             lir::ElementKind::Line(lir::Line {
-                                start: origin + start,
+                                start,
                                 end,
-                                width,
+                                width: self.width,
                             })
                         }
                     } else {
-                        if !dangling_contlines_chained {
+                        if !self.dangling_contlines_chained {
                             errors.push(Error {
                                 cmd_pos: op.cmd_pos,
                                 cmd_index: op_i,
                                 kind: ErrorKind::DanglingContinuedLine,
                             });
 
-                            dangling_contlines_chained = true;
+                            self.dangling_contlines_chained = true;
                         }
 
-                        continue;
+                        return None;
                     }
                 }
                 hir::OperationKind::DrawDisk(draw_disk) => {
-                    let centre = origin + draw_disk.centre;
+                    let centre = self.origin + draw_disk.centre;
 
-                    last_point = Some(centre);
+                    self.last_point = Some(centre);
 
                     lir::ElementKind::Disk(lir::Disk {
                         centre,
-                        radius: width / 2.,
+                        radius: self.width / 2.,
                     })
                 }
                 hir::OperationKind::DrawRectangle(draw_rectangle) => {
-                    let bottom_left = origin + draw_rectangle.centre - draw_rectangle.sizes;
+                    let bottom_left = self.origin + draw_rectangle.centre - draw_rectangle.sizes;
 
                     if draw_rectangle.filled {
-                        last_point = None;
+                        self.last_point = None;
                     } else {
                         let top_right = draw_rectangle.centre + draw_rectangle.sizes;
-                        last_point = Some(origin + top_right);
+                        self.last_point = Some(self.origin + top_right);
                     }
 
                     lir::ElementKind::Rectangle(lir::Rectangle {
                         bottom_left,
                         sizes: draw_rectangle.sizes * 2.,
                         filled: draw_rectangle.filled,
-                        outline_width: width,
+                        outline_width: self.width,
                     })
                 }
                 hir::OperationKind::DrawTriangle(draw_triangle) => {
-                    last_point = None;
+                    self.last_point = None;
 
                     if draw_triangle.a == draw_triangle.b && draw_triangle.b == draw_triangle.c {
                         lir::ElementKind::Disk(lir::Disk {
-                            centre: origin + draw_triangle.a,
-                            radius: width / 2.,
+                            centre: self.origin + draw_triangle.a,
+                            radius: self.width / 2.,
                         })
                     } else if draw_triangle.a == draw_triangle.b {
                         lir::ElementKind::Line(lir::Line {
-                            start: origin + draw_triangle.a,
-                            end: origin + draw_triangle.c,
-                            width,
+                            start: self.origin + draw_triangle.a,
+                            end: self.origin + draw_triangle.c,
+                            width: self.width,
                         })
                     } else if draw_triangle.b == draw_triangle.c {
                         lir::ElementKind::Line(lir::Line {
-                            start: origin + draw_triangle.b,
-                            end: origin + draw_triangle.a,
-                            width,
+                            start: self.origin + draw_triangle.b,
+                            end: self.origin + draw_triangle.a,
+                            width: self.width,
                         })
                     } else if draw_triangle.c == draw_triangle.a {
                         lir::ElementKind::Line(lir::Line {
-                            start: origin + draw_triangle.c,
-                            end: origin + draw_triangle.b,
-                            width,
+                            start: self.origin + draw_triangle.c,
+                            end: self.origin + draw_triangle.b,
+                            width: self.width,
                         })
                     } else {
                         lir::ElementKind::Triangle(lir::Triangle {
-                            a: origin + draw_triangle.a,
-                            b: origin + draw_triangle.b,
-                            c: origin + draw_triangle.c,
-                            outline_width: width,
+                            a: self.origin + draw_triangle.a,
+                            b: self.origin + draw_triangle.b,
+                            c: self.origin + draw_triangle.c,
+                            outline_width: self.width,
                         })
                     }
                 }
                 hir::OperationKind::MoveCentre(move_centre) => {
-                    origin += move_centre.change;
-                    continue;
+                    self.origin += move_centre.change;
+                    return None;
                 }
                 hir::OperationKind::ResetCentre(hir::ResetCentre) => {
-                    origin = Vector::ZERO;
-                    continue;
+                    self.origin = Vector::ZERO;
+                    return None;
                 }
                 hir::OperationKind::DrawArc(draw_arc) => {
-                    let direction = (draw_arc.direction * 10.).to_radians();
+                    let direction =
+                        (draw_arc.direction * self.compat.arc_direction_scale).to_radians();
                     let arm_angle = draw_arc.arm_angle.to_radians();
 
                     let start_angle = std::f32::consts::FRAC_PI_2 - (direction + arm_angle);
                     let end_angle = std::f32::consts::FRAC_PI_2 - (direction - arm_angle);
 
-                    let centre = origin + draw_arc.centre;
+                    let centre = self.origin + draw_arc.centre;
 
                     let start_point =
                         centre + Vector::new_from_length(draw_arc.radius, start_angle);
-                    last_point = Some(start_point);
+                    self.last_point = Some(start_point);
 
                     // FIXME do relative margin
                     if (draw_arc.arm_angle - 180.).abs() < 1e-7 {
                         lir::ElementKind::Circle(lir::Circle {
                             centre,
                             radius: draw_arc.radius,
-                            width,
+                            width: self.width,
                         })
                     } else if draw_arc.radius.abs() < 1e-9 || draw_arc.arm_angle.abs() < 1e-9 {
                         lir::ElementKind::Disk(lir::Disk {
                             centre: start_point,
-                            radius: width / 2.,
+                            radius: self.width / 2.,
                         })
                     } else {
                         lir::ElementKind::Arc(lir::Arc {
                             centre,
                             radius: draw_arc.radius,
-                            width,
+                            width: self.width,
                             start_angle,
                             end_angle,
                         })
@@ -199,8 +263,8 @@ pub fn resolve(hir: &hir::IconHir) -> (lir::IconLir, Vec<Error>) {
 
                     let direction = -draw_ellipse.direction.to_radians();
 
-                    let centre = origin + draw_ellipse.centre;
-                    last_point = Some(
+                    let centre = self.origin + draw_ellipse.centre;
+                    self.last_point = Some(
                         centre
                             + Vector::new_from_length(
                                 minor,
@@ -211,7 +275,7 @@ pub fn resolve(hir: &hir::IconHir) -> (lir::IconLir, Vec<Error>) {
                     if draw_ellipse.major == 0. {
                         lir::ElementKind::Disk(lir::Disk {
                             centre,
-                            radius: width / 2.,
+                            radius: self.width / 2.,
                         })
                     } else if draw_ellipse.ratio == 0. {
                         let to_end = Vector::new_from_length(draw_ellipse.major, direction);
@@ -219,7 +283,14 @@ pub fn resolve(hir: &hir::IconHir) -> (lir::IconLir, Vec<Error>) {
                         lir::ElementKind::Line(lir::Line {
                             start: centre - to_end,
                             end: centre + to_end,
-                            width,
+                            width: self.width,
+                        })
+                    // FIXME do relative margin
+                    } else if (draw_ellipse.major - minor).abs() <= 1e-7 {
+                        lir::ElementKind::Circle(lir::Circle {
+                            centre,
+                            radius: draw_ellipse.major,
+                            width: self.width,
                         })
                     } else {
                         lir::ElementKind::Ellipse(lir::Ellipse {
@@ -229,27 +300,25 @@ pub fn resolve(hir: &hir::IconHir) -> (lir::IconLir, Vec<Error>) {
                                 y: minor,
                             },
                             direction,
-                            outline_width: width,
+                            outline_width: self.width,
                         })
                     }
                 }
                 hir::OperationKind::DrawCurve(draw_curve) => {
-                    let end = origin + draw_curve.end;
+                    let end = self.origin + draw_curve.end;
 
-                    last_point = Some(end);
+                    self.last_point = Some(end);
 
                     lir::ElementKind::Curve(lir::Curve {
-                        start: origin + draw_curve.start,
+                        start: self.origin + draw_curve.start,
                         end,
-                        control: origin + draw_curve.control,
-                        width,
+                        control: self.origin + draw_curve.control,
+                        width: self.width,
                     })
                 }
             },
         };
 
-        elements.push(el);
+        Some(el)
     }
-
-    (lir::IconLir { elements }, errors)
 }
@@ -0,0 +1,467 @@
+use std::{fmt, str::FromStr};
+
+use rotur_icn_lexer::{
+    display::PosDisplay,
+    token::{Loc, Pos},
+};
+use rotur_icn_units::{Colour, Number, Vector};
+
+use super::lir;
+
+/// Read the LIR back from its displayed form
+///
+/// The header & the `- ` before each element are optional, blank lines are skipped.
+/// Colours may carry an alpha (`#rrggbbaa`), which isn't displayed.
+/// Elements the resolver would've simplified are rejected, as they can't be rendered.
+impl FromStr for lir::IconLir {
+    type Err = ReadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut elements = Vec::new();
+        let mut header: Option<(usize, Pos)> = None;
+        let mut no_elements = false;
+
+        let mut start = Loc {
+            line: 0,
+            col: 0,
+            byte_idx: 0,
+        };
+
+        for line in s.split_inclusive('\n') {
+            let mut cursor = Cursor {
+                rest: line.trim_end(),
+                loc: start,
+            };
+
+            start.line += 1;
+            start.byte_idx += line.len();
+
+            cursor.skip_spaces();
+
+            if cursor.rest.is_empty() {
+                continue;
+            }
+
+            let line_start = cursor.loc;
+
+            if cursor.eat("Icon elements (") {
+                if header.is_some() || !elements.is_empty() || no_elements {
+                    return Err(cursor.error_from(line_start, ReadErrorKind::MisplacedHeader));
+                }
+
+                let count = cursor.count()?;
+                cursor.expect(" total):")?;
+                header = Some((count, (line_start, cursor.loc)));
+            } else if cursor.eat("<no elements>") {
+                if no_elements || !elements.is_empty() {
+                    return Err(cursor.error_from(line_start, ReadErrorKind::MisplacedNoElements));
+                }
+
+                no_elements = true;
+            } else {
+                if no_elements {
+                    return Err(ReadError {
+                        pos: (line_start, cursor.end()),
+                        kind: ReadErrorKind::MisplacedNoElements,
+                    });
+                }
+
+                cursor.eat("- ");
+                cursor.skip_spaces();
+                elements.push(cursor.element()?);
+            }
+
+            cursor.skip_spaces();
+
+            if !cursor.rest.is_empty() {
+                let end = cursor.end();
+                return Err(ReadError {
+                    pos: (cursor.loc, end),
+                    kind: ReadErrorKind::TrailingInput,
+                });
+            }
+        }
+
+        if let Some((expected, pos)) = header
+            && expected != elements.len()
+        {
+            return Err(ReadError {
+                pos,
+                kind: ReadErrorKind::CountMismatch {
+                    expected,
+                    found: elements.len(),
+                },
+            });
+        }
+
+        Ok(Self { elements })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadError {
+    pub pos: Pos,
+    pub kind: ReadErrorKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum ReadErrorKind {
+    /// What was expected instead
+    Expected(&'static str),
+    UnknownElement,
+    InvalidNumber,
+    InvalidColour,
+    TrailingInput,
+    MisplacedHeader,
+    /// `<no elements>` along with elements or once more
+    MisplacedNoElements,
+    CountMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// The resolver would've simplified the element into another one
+    Degenerate,
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", PosDisplay(&self.pos), self.kind)
+    }
+}
+
+impl fmt::Display for ReadErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expected(expected) => write!(f, "expected {expected}"),
+            Self::UnknownElement => write!(f, "found an unknown element"),
+            Self::InvalidNumber => write!(f, "found an invalid number"),
+            Self::InvalidColour => write!(f, "found an invalid colour"),
+            Self::TrailingInput => write!(f, "found trailing input after the element"),
+            Self::MisplacedHeader => write!(f, "the header isn't the first line"),
+            Self::MisplacedNoElements => {
+                write!(f, "`<no elements>` can only be on its own after the header")
+            }
+            Self::CountMismatch { expected, found } => write!(
+                f,
+                "the header counts {expected} elements, but there are {found}"
+            ),
+            Self::Degenerate => write!(f, "the element should've been resolved to another one"),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+/// The rest of a line, along with where it starts
+struct Cursor<'s> {
+    rest: &'s str,
+    loc: Loc,
+}
+
+impl Cursor<'_> {
+    #[expect(clippy::too_many_lines, reason = "a flat match over every element")]
+    fn element(&mut self) -> Result<lir::Element, ReadError> {
+        let start = self.loc;
+        let name = self.take_while(|ch| ch.is_ascii_alphabetic());
+
+        let kind = match name {
+            "line" => {
+                let start = self.vector()?;
+                self.separator()?;
+                let end = self.vector()?;
+                let width = self.prefixed_number("w")?;
+
+                lir::ElementKind::Line(lir::Line { start, end, width })
+            }
+            "disk" => {
+                let centre = self.vector()?;
+                let radius = self.prefixed_number("r")?;
+
+                lir::ElementKind::Disk(lir::Disk { centre, radius })
+            }
+            "circle" => {
+                let centre = self.vector()?;
+                let radius = self.prefixed_number("r")?;
+                let width = self.prefixed_number("w")?;
+
+                lir::ElementKind::Circle(lir::Circle {
+                    centre,
+                    radius,
+                    width,
+                })
+            }
+            "rectangle" => {
+                let bottom_left = self.vector()?;
+                self.skip_spaces();
+                self.expect("s")?;
+                let sizes = self.vector()?;
+                self.skip_spaces();
+                let filled = if self.eat("filled") {
+                    true
+                } else if self.eat("hollow") {
+                    false
+                } else {
+                    return Err(self.error(ReadErrorKind::Expected("`filled` or `hollow`")));
+                };
+                let outline_width = self.prefixed_number("ow")?;
+
+                lir::ElementKind::Rectangle(lir::Rectangle {
+                    bottom_left,
+                    sizes,
+                    filled,
+                    outline_width,
+                })
+            }
+            "triangle" => {
+                let a = self.vector()?;
+                self.separator()?;
+                let b = self.vector()?;
+                self.separator()?;
+                let c = self.vector()?;
+                let outline_width = self.prefixed_number("ow")?;
+
+                lir::ElementKind::Triangle(lir::Triangle {
+                    a,
+                    b,
+                    c,
+                    outline_width,
+                })
+            }
+            "arc" => {
+                let centre = self.vector()?;
+                let radius = self.prefixed_number("r")?;
+                let width = self.prefixed_number("w")?;
+                let start_angle = self.angle()?;
+                self.separator()?;
+                let end_angle = self.angle()?;
+
+                lir::ElementKind::Arc(lir::Arc {
+                    centre,
+                    radius,
+                    width,
+                    start_angle,
+                    end_angle,
+                })
+            }
+            "ellipse" => {
+                let centre = self.vector()?;
+                self.skip_spaces();
+                self.expect("s")?;
+                let axis = self.vector()?;
+                self.skip_spaces();
+                self.expect("dir")?;
+                let direction = self.angle()?;
+                let outline_width = self.prefixed_number("ow")?;
+
+                lir::ElementKind::Ellipse(lir::Ellipse {
+                    centre,
+                    axis,
+                    direction,
+                    outline_width,
+                })
+            }
+            "curve" => {
+                let start = self.vector()?;
+                self.separator()?;
+                let control = self.vector()?;
+                self.separator()?;
+                let end = self.vector()?;
+                let width = self.prefixed_number("w")?;
+
+                lir::ElementKind::Curve(lir::Curve {
+                    start,
+                    control,
+                    end,
+                    width,
+                })
+            }
+            "" => return Err(self.error(ReadErrorKind::UnknownElement)),
+            _ => return Err(self.error_from(start, ReadErrorKind::UnknownElement)),
+        };
+
+        if is_degenerate(&kind) {
+            return Err(self.error_from(start, ReadErrorKind::Degenerate));
+        }
+
+        self.skip_spaces();
+        self.expect("col#")?;
+        let colour = self.colour()?;
+
+        Ok(lir::Element { colour, kind })
+    }
+
+    fn vector(&mut self) -> Result<Vector, ReadError> {
+        self.skip_spaces();
+        self.expect("(")?;
+        let x = self.number()?;
+        self.expect(",")?;
+        self.skip_spaces();
+        let y = self.number()?;
+        self.expect(")")?;
+
+        Ok(Vector { x, y })
+    }
+
+    fn prefixed_number(&mut self, prefix: &'static str) -> Result<Number, ReadError> {
+        self.skip_spaces();
+        self.expect(prefix)?;
+        self.number()
+    }
+
+    fn angle(&mut self) -> Result<Number, ReadError> {
+        self.skip_spaces();
+        let angle = self.number()?;
+        self.expect("rad")?;
+
+        Ok(angle)
+    }
+
+    fn separator(&mut self) -> Result<(), ReadError> {
+        self.skip_spaces();
+        self.expect("-")
+    }
+
+    fn number(&mut self) -> Result<Number, ReadError> {
+        let start = self.loc;
+        let number = self.take_while(|ch| ch.is_ascii_digit() || "+-.eE".contains(ch));
+
+        if number.is_empty() {
+            return Err(self.error(ReadErrorKind::Expected("a number")));
+        }
+
+        number
+            .parse()
+            .map_err(|_| self.error_from(start, ReadErrorKind::InvalidNumber))
+    }
+
+    fn count(&mut self) -> Result<usize, ReadError> {
+        let start = self.loc;
+        let count = self.take_while(|ch| ch.is_ascii_digit());
+
+        if count.is_empty() {
+            return Err(self.error(ReadErrorKind::Expected("a count")));
+        }
+
+        count
+            .parse()
+            .map_err(|_| self.error_from(start, ReadErrorKind::InvalidNumber))
+    }
+
+    fn colour(&mut self) -> Result<Colour, ReadError> {
+        let start = self.loc;
+        let hex = self.take_while(|ch| ch.is_ascii_hexdigit());
+
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+        let colour = match hex.len() {
+            6 => channel(0)
+                .zip(channel(2))
+                .zip(channel(4))
+                .map(|((r, g), b)| Colour { r, g, b, a: 0xff }),
+            8 => channel(0)
+                .zip(channel(2))
+                .zip(channel(4))
+                .zip(channel(6))
+                .map(|(((r, g), b), a)| Colour { r, g, b, a }),
+            _ => None,
+        };
+
+        colour.ok_or_else(|| self.error_from(start, ReadErrorKind::InvalidColour))
+    }
+
+    fn expect(&mut self, expected: &'static str) -> Result<(), ReadError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(ReadErrorKind::Expected(expected)))
+        }
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        if self.rest.starts_with(expected) {
+            self.advance(expected.len());
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        self.take_while(|ch| ch == ' ' || ch == '\t');
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &str {
+        let len = self.rest.find(|ch| !pred(ch)).unwrap_or(self.rest.len());
+
+        let taken = &self.rest[..len];
+        self.advance(len);
+
+        taken
+    }
+
+    fn advance(&mut self, len: usize) {
+        let (taken, rest) = self.rest.split_at(len);
+
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "a line isn't longer than u32::MAX chars"
+        )]
+        let chars = taken.chars().count() as u32;
+
+        self.rest = rest;
+        self.loc.col += chars;
+        self.loc.byte_idx += len;
+    }
+
+    /// Where the line ends
+    fn end(&self) -> Loc {
+        let mut end = Cursor {
+            rest: self.rest,
+            loc: self.loc,
+        };
+        end.advance(self.rest.len());
+
+        end.loc
+    }
+
+    /// Error on the next char
+    fn error(&self, kind: ReadErrorKind) -> ReadError {
+        let mut end = Cursor {
+            rest: self.rest,
+            loc: self.loc,
+        };
+        end.advance(self.rest.chars().next().map_or(0, char::len_utf8));
+
+        ReadError {
+            pos: (self.loc, end.loc),
+            kind,
+        }
+    }
+
+    /// Error on everything read since the start
+    fn error_from(&self, start: Loc, kind: ReadErrorKind) -> ReadError {
+        ReadError {
+            pos: (start, self.loc),
+            kind,
+        }
+    }
+}
+
+/// Whether the renderer can't draw the element, as it'd been resolved to a simpler one
+fn is_degenerate(kind: &lir::ElementKind) -> bool {
+    match kind {
+        lir::ElementKind::Line(line) => line.start == line.end,
+        lir::ElementKind::Triangle(tri) => tri.a == tri.b || tri.b == tri.c || tri.c == tri.a,
+        // FIXME make float comp margin relative
+        lir::ElementKind::Arc(arc) => (arc.start_angle - arc.end_angle).abs() <= 1e-7,
+        lir::ElementKind::Ellipse(ellipse) => {
+            ellipse.axis.x == 0.
+                || ellipse.axis.y == 0.
+                || (ellipse.axis.x - ellipse.axis.y).abs() <= 1e-7
+        }
+        lir::ElementKind::Disk(_)
+        | lir::ElementKind::Circle(_)
+        | lir::ElementKind::Rectangle(_)
+        | lir::ElementKind::Curve(_) => false,
+    }
+}
@@ -0,0 +1,102 @@
+use rotur_icn_resolver::{ReadError, ReadErrorKind, lir};
+
+const DISK: &str = "- disk (0, 0) r2.5 col#ffffff";
+
+fn read_error(src: &str) -> ReadError {
+    src.parse::<lir::IconLir>()
+        .expect_err("the LIR shouldn't read")
+}
+
+/// Lines & columns the error spans
+fn span(err: &ReadError) -> ((usize, usize), (usize, usize)) {
+    let (start, end) = err.pos;
+
+    (
+        (start.line as usize, start.col as usize),
+        (end.line as usize, end.col as usize),
+    )
+}
+
+#[test]
+fn no_elements_after_elements_is_rejected() {
+    let err = read_error(&format!(
+        "Icon elements (1 total):\n{DISK}\n<no elements>\n"
+    ));
+
+    assert!(matches!(err.kind, ReadErrorKind::MisplacedNoElements));
+    assert_eq!(span(&err), ((2, 0), (2, 13)));
+}
+
+#[test]
+fn elements_after_no_elements_are_rejected() {
+    let err = read_error(&format!("<no elements>\n\n  {DISK}\n"));
+
+    assert!(matches!(err.kind, ReadErrorKind::MisplacedNoElements));
+    assert_eq!(span(&err), ((2, 2), (2, 31)));
+}
+
+#[test]
+fn misplaced_header_spans_its_start() {
+    let err = read_error(&format!("{DISK}\nIcon elements (1 total):\n"));
+
+    assert!(matches!(err.kind, ReadErrorKind::MisplacedHeader));
+    assert_eq!(span(&err), ((1, 0), (1, 15)));
+}
+
+#[test]
+fn count_mismatch_spans_the_header() {
+    let err = read_error(&format!("Icon elements (2 total):\n{DISK}\n"));
+
+    assert!(matches!(
+        err.kind,
+        ReadErrorKind::CountMismatch {
+            expected: 2,
+            found: 1
+        }
+    ));
+    assert_eq!(span(&err), ((0, 0), (0, 24)));
+}
+
+#[test]
+fn invalid_number_spans_the_number() {
+    let err = read_error("- disk (0, 0) r2..5 col#ffffff");
+
+    assert!(matches!(err.kind, ReadErrorKind::InvalidNumber));
+    assert_eq!(span(&err), ((0, 15), (0, 19)));
+}
+
+#[test]
+fn trailing_input_spans_the_rest_of_the_line() {
+    let err = read_error(&format!("{DISK} extra  \n"));
+
+    assert!(matches!(err.kind, ReadErrorKind::TrailingInput));
+    assert_eq!(span(&err), ((0, 30), (0, 35)));
+}
+
+#[test]
+fn unknown_element_spans_its_name() {
+    let err = read_error("\n- blob (0, 0) col#ffffff");
+
+    assert!(matches!(err.kind, ReadErrorKind::UnknownElement));
+    assert_eq!(span(&err), ((1, 2), (1, 6)));
+}
+
+#[test]
+fn angles_read_back_exactly() {
+    let icon = lir::IconLir {
+        elements: vec![lir::Element {
+            kind: lir::ElementKind::Arc(lir::Arc {
+                centre: rotur_icn_units::Vector::ZERO,
+                radius: 6.,
+                width: 1.,
+                start_angle: std::f32::consts::FRAC_PI_4,
+                end_angle: 3. * std::f32::consts::FRAC_PI_4,
+            }),
+            colour: rotur_icn_units::Colour::WHITE,
+        }],
+    };
+
+    let read = icon.to_string().parse::<lir::IconLir>();
+
+    assert!(read.is_ok_and(|read| read == icon), "{icon}");
+}
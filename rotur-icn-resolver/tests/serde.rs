@@ -0,0 +1,24 @@
+#![cfg(feature = "serde")]
+
+use rand::{SeedableRng as _, distr::Distribution as _, rngs::StdRng};
+
+use rotur_icn_lowerer::hir;
+use rotur_icn_rand::IcnSampler;
+use rotur_icn_resolver::lir;
+
+#[test]
+fn lir_round_trips_through_json() {
+    let sampler = IcnSampler::default();
+    let mut rng = StdRng::seed_from_u64(0x1c4);
+
+    for _ in 0..200 {
+        let icon: hir::IconHir = sampler.sample(&mut rng);
+        let (icon, _) = rotur_icn_resolver::resolve(&icon);
+
+        let json = serde_json::to_string(&icon).expect("the LIR should serialise");
+        let round_tripped: lir::IconLir =
+            serde_json::from_str(&json).expect("the LIR should deserialise");
+
+        assert_eq!(icon, round_tripped, "the LIR changed through\n{json}");
+    }
+}
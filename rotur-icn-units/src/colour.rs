@@ -3,6 +3,11 @@ mod display;
 // reversed order for a noop u32 -> Colour
 #[cfg(target_endian = "little")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Rgba", into = "Rgba")
+)]
 pub struct Colour {
     pub a: u8,
     pub b: u8,
@@ -12,6 +17,11 @@ pub struct Colour {
 
 #[cfg(target_endian = "big")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Rgba", into = "Rgba")
+)]
 pub struct Colour {
     pub r: u8,
     pub g: u8,
@@ -19,6 +29,35 @@ pub struct Colour {
     pub a: u8,
 }
 
+/// Serialised the same regardless of endianness
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Rgba {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+}
+
+#[cfg(feature = "serde")]
+impl From<Rgba> for Colour {
+    fn from(rgba: Rgba) -> Self {
+        Self {
+            r: rgba.r,
+            g: rgba.g,
+            b: rgba.b,
+            a: rgba.a,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Colour> for Rgba {
+    fn from(Colour { r, g, b, a }: Colour) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
 impl Default for Colour {
     fn default() -> Self {
         Self {
@@ -4,6 +4,7 @@ mod display;
 mod maths;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector {
     pub x: Number,
     pub y: Number,
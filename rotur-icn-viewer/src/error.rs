@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, path::PathBuf};
 
 pub const BASE_ERROR_EXIT_CODE: i32 = 90;
 pub const EXIT_CODE_FOUND_ERRORS: i32 = BASE_ERROR_EXIT_CODE;
@@ -6,6 +6,11 @@ pub const EXIT_CODE_FAILED_OPEN_FILE: i32 = BASE_ERROR_EXIT_CODE + 1;
 pub const EXIT_CODE_FAILED_READ_FILE: i32 = BASE_ERROR_EXIT_CODE + 2;
 pub const EXIT_CODE_FAILED_WRITE_FILE: i32 = BASE_ERROR_EXIT_CODE + 3;
 pub const EXIT_CODE_FAILED_DISPLAY_DIAGNOSTICS: i32 = BASE_ERROR_EXIT_CODE + 4;
+pub const EXIT_CODE_FAILED_ENCODE_IMAGE: i32 = BASE_ERROR_EXIT_CODE + 5;
+pub const EXIT_CODE_FAILED_RUN_GUI: i32 = BASE_ERROR_EXIT_CODE + 6;
+pub const EXIT_CODE_INVALID_OPTIONS: i32 = BASE_ERROR_EXIT_CODE + 7;
+pub const EXIT_CODE_NOT_FORMATTED: i32 = BASE_ERROR_EXIT_CODE + 8;
+pub const EXIT_CODE_FAILED_RENDER: i32 = BASE_ERROR_EXIT_CODE + 9;
 
 #[derive(Debug)]
 pub enum FailureError {
@@ -13,6 +18,18 @@ pub enum FailureError {
     ReadFile(std::io::Error),
     WriteFile(std::io::Error),
     DisplayDiagnostics(codespan_reporting::files::Error),
+    EncodeImage(png::EncodingError),
+    Render(rotur_icn_renderer::cpu::RenderError),
+    CreateDir(std::io::Error),
+    InvalidGlob(glob::PatternError),
+    WatchWithoutPaths,
+    BatchWithoutSave,
+    JsonDumpWithoutPath,
+    ConflictingOutputs(PathBuf),
+    #[cfg(feature = "gui")]
+    RunGui(eframe::Error),
+    #[cfg(not(feature = "gui"))]
+    GuiUnavailable,
 }
 
 impl fmt::Display for FailureError {
@@ -22,6 +39,24 @@ impl fmt::Display for FailureError {
             Self::ReadFile(_) => write!(f, "failed to read from a file"),
             Self::WriteFile(_) => write!(f, "failed to write to a file"),
             Self::DisplayDiagnostics(_) => write!(f, "failed to display diagnostics"),
+            Self::EncodeImage(_) => write!(f, "failed to encode the image"),
+            Self::Render(_) => write!(f, "failed to render the ICN"),
+            Self::CreateDir(_) => write!(f, "failed to create a directory"),
+            Self::InvalidGlob(_) => write!(f, "invalid glob pattern"),
+            Self::WatchWithoutPaths => write!(f, "watching needs a single ICN path and --save"),
+            Self::BatchWithoutSave => write!(f, "exporting several ICNs needs --save"),
+            Self::JsonDumpWithoutPath => {
+                write!(f, "dumping the representations as JSON needs --dump-to")
+            }
+            Self::ConflictingOutputs(path) => write!(
+                f,
+                "several ICNs would be saved to {}, as they share a name",
+                path.display()
+            ),
+            #[cfg(feature = "gui")]
+            Self::RunGui(_) => write!(f, "failed to run the GUI"),
+            #[cfg(not(feature = "gui"))]
+            Self::GuiUnavailable => write!(f, "the viewer was built without the GUI"),
         }
     }
 }
@@ -29,8 +64,22 @@ impl fmt::Display for FailureError {
 impl std::error::Error for FailureError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::OpenFile(err) | Self::ReadFile(err) | Self::WriteFile(err) => Some(err),
+            Self::OpenFile(err)
+            | Self::ReadFile(err)
+            | Self::WriteFile(err)
+            | Self::CreateDir(err) => Some(err),
             Self::DisplayDiagnostics(err) => Some(err),
+            Self::EncodeImage(err) => Some(err),
+            Self::Render(err) => Some(err),
+            Self::InvalidGlob(err) => Some(err),
+            Self::WatchWithoutPaths
+            | Self::BatchWithoutSave
+            | Self::JsonDumpWithoutPath
+            | Self::ConflictingOutputs(_) => None,
+            #[cfg(feature = "gui")]
+            Self::RunGui(err) => Some(err),
+            #[cfg(not(feature = "gui"))]
+            Self::GuiUnavailable => None,
         }
     }
 }
@@ -1,7 +1,9 @@
 use std::{
-    io::{self, Write},
-    path::Path,
-    time::Instant,
+    collections::HashSet,
+    fmt::Write as _,
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
 
 use ansi_term::{Color, Style};
@@ -9,26 +11,316 @@ use codespan_reporting::{
     files::SimpleFile,
     term::termcolor::{ColorChoice, StandardStream},
 };
-use rotur_icn_pipeline::Errors;
-use rotur_icn_renderer::{cpu::Renderer, fitter};
+use rotur_icn_pipeline::{Compat, Dialect, Errors, LintConfig};
+use rotur_icn_renderer::{
+    cpu::{RenderError, Renderer},
+    fitter, svg,
+};
 use rotur_icn_resolver::lir;
 use rotur_icn_units::{Colour, Vector};
 
 use crate::{
     abort::abort,
     error::{
-        EXIT_CODE_FAILED_DISPLAY_DIAGNOSTICS, EXIT_CODE_FAILED_OPEN_FILE,
-        EXIT_CODE_FAILED_READ_FILE, EXIT_CODE_FAILED_WRITE_FILE, EXIT_CODE_FOUND_ERRORS,
+        EXIT_CODE_FAILED_DISPLAY_DIAGNOSTICS, EXIT_CODE_FAILED_ENCODE_IMAGE,
+        EXIT_CODE_FAILED_OPEN_FILE, EXIT_CODE_FAILED_READ_FILE, EXIT_CODE_FAILED_RENDER,
+        EXIT_CODE_FAILED_WRITE_FILE, EXIT_CODE_FOUND_ERRORS, EXIT_CODE_INVALID_OPTIONS,
         FailureError,
     },
-    options::ExportOptions,
+    options::{DiagnosticsFormat, DumpFormat, ExportFormat, ExportOptions},
 };
 
-pub fn export(
-    ExportOptions {
+pub fn export(opts: &ExportOptions) {
+    let mut renderer = Renderer::new(Vector::ZERO, opts.scale, Vector::ZERO, opts.background);
+    renderer.anti_aliasing = opts.anti_aliasing;
+    if let Some(pixel_budget) = opts.pixel_budget {
+        renderer.pixel_budget = pixel_budget;
+    }
+
+    // it'd be mixed in with the diagnostics on stderr, so that it couldn't be parsed
+    if opts.dump_format == DumpFormat::Json
+        && opts.dump_to.is_none()
+        && (opts.ast || opts.hir || opts.lir)
+    {
+        abort(
+            &FailureError::JsonDumpWithoutPath,
+            EXIT_CODE_INVALID_OPTIONS,
+        )
+    }
+
+    // the dumps of every ICN are appended to it, starting out empty
+    if let Some(dump_to) = &opts.dump_to {
+        std::fs::File::create(dump_to)
+            .unwrap_or_else(|err| abort(&FailureError::OpenFile(err), EXIT_CODE_FAILED_OPEN_FILE));
+    }
+
+    if opts.icon.is_empty() {
+        if opts.watch {
+            abort(&FailureError::WatchWithoutPaths, EXIT_CODE_INVALID_OPTIONS)
+        }
+
+        let save_path = opts
+            .save
+            .as_deref()
+            .map(|save| output_path(opts.format, save, None, false));
+        let icon_src = read(None);
+        let errors = export_src(opts, &mut renderer, None, &icon_src, save_path.as_deref())
+            .unwrap_or_else(|err| abort(&FailureError::Render(err), EXIT_CODE_FAILED_RENDER));
+
+        if errors > 0 {
+            std::process::exit(EXIT_CODE_FOUND_ERRORS)
+        }
+
+        return;
+    }
+
+    let icon_paths = expand_globs(&opts.icon);
+
+    if opts.watch {
+        let [icon_path] = icon_paths.as_slice() else {
+            abort(&FailureError::WatchWithoutPaths, EXIT_CODE_INVALID_OPTIONS)
+        };
+
+        watch(opts, &mut renderer, icon_path);
+    }
+
+    let batch = icon_paths.len() > 1;
+
+    if batch && opts.save.is_none() {
+        abort(&FailureError::BatchWithoutSave, EXIT_CODE_INVALID_OPTIONS)
+    }
+
+    let save_paths = icon_paths
+        .iter()
+        .map(|icon_path| {
+            opts.save
+                .as_deref()
+                .map(|save| output_path(opts.format, save, Some(icon_path), batch))
+        })
+        .collect::<Vec<_>>();
+
+    // ICNs of the same name from different directories would overwrite each other
+    let mut seen = HashSet::new();
+    if let Some(conflict) = save_paths.iter().flatten().find(|path| !seen.insert(*path)) {
+        abort(
+            &FailureError::ConflictingOutputs(conflict.clone()),
+            EXIT_CODE_INVALID_OPTIONS,
+        )
+    }
+
+    let mut failed = Vec::new();
+
+    for (icon_path, save_path) in icon_paths.iter().zip(save_paths) {
+        if batch && let Some(parent) = save_path.as_deref().and_then(Path::parent) {
+            std::fs::create_dir_all(parent).unwrap_or_else(|err| {
+                abort(&FailureError::CreateDir(err), EXIT_CODE_FAILED_WRITE_FILE)
+            });
+        }
+
+        let icon_src = read(Some(icon_path));
+        let errors = match export_src(
+            opts,
+            &mut renderer,
+            Some(icon_path),
+            &icon_src,
+            save_path.as_deref(),
+        ) {
+            Ok(errors) => errors,
+            Err(err) if batch => {
+                // the other ICNs are still exported, counting this one as failed
+                eprintln!(
+                    "{} {}: {err}",
+                    Color::Red.paint("Failed to render"),
+                    icon_path.display()
+                );
+                1
+            }
+            Err(err) => abort(&FailureError::Render(err), EXIT_CODE_FAILED_RENDER),
+        };
+
+        if errors > 0 {
+            failed.push((icon_path, errors));
+        }
+    }
+
+    if batch {
+        display_summary(icon_paths.len(), &failed);
+    }
+
+    if !failed.is_empty() {
+        std::process::exit(EXIT_CODE_FOUND_ERRORS)
+    }
+}
+
+/// Replace arguments containing glob patterns with the paths they match
+pub fn expand_globs(patterns: &[PathBuf]) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    for pattern in patterns {
+        let Some(pattern) = pattern.to_str().filter(|p| p.contains(['*', '?', '['])) else {
+            paths.push(pattern.clone());
+            continue;
+        };
+
+        let matches = glob::glob(pattern).unwrap_or_else(|err| {
+            abort(&FailureError::InvalidGlob(err), EXIT_CODE_INVALID_OPTIONS)
+        });
+
+        let len_before = paths.len();
+
+        for path in matches {
+            paths.push(path.unwrap_or_else(|err| {
+                abort(
+                    &FailureError::ReadFile(err.into()),
+                    EXIT_CODE_FAILED_READ_FILE,
+                )
+            }));
+        }
+
+        if paths.len() == len_before {
+            eprintln!("{} {pattern}", Color::Yellow.paint("No ICN files match"));
+        }
+    }
+
+    paths
+}
+
+/// Choose where to save the ICN's image
+///
+/// `{stem}` in the save path is replaced by the ICN's file name without extension.
+/// Without it, several ICNs are saved into the save path as a directory.
+/// A single ICN is saved there too, if it's an existing directory.
+fn output_path(
+    format: Option<ExportFormat>,
+    save: &Path,
+    icon_path: Option<&Path>,
+    batch: bool,
+) -> PathBuf {
+    let stem = icon_path
+        .and_then(Path::file_stem)
+        .map_or("stdin".into(), |stem| stem.to_string_lossy());
+
+    let save_str = save.to_string_lossy();
+
+    if save_str.contains("{stem}") {
+        PathBuf::from(save_str.replace("{stem}", &stem))
+    } else if batch || save.is_dir() {
+        let format = format.unwrap_or(ExportFormat::Pam);
+        save.join(format!("{stem}.{}", format.extension()))
+    } else {
+        save.to_path_buf()
+    }
+}
+
+fn display_summary(total: usize, failed: &[(&PathBuf, usize)]) {
+    for (path, errors) in failed {
+        eprintln!(
+            "{} {} {}",
+            Style::new().bold().paint(path.display().to_string()),
+            Color::Red.paint("has"),
+            Color::Red.paint(format!("{errors} errors")),
+        );
+    }
+
+    eprintln!(
+        "{} {} {} {} {}",
+        Color::Green.paint("Exported"),
+        Style::new().bold().paint(total.to_string()),
+        Color::Green.paint("ICNs,"),
+        Style::new().bold().paint(failed.len().to_string()),
+        Color::Red.paint("with errors"),
+    );
+}
+
+/// Re-export the ICN every time its file is modified, never exiting on its own
+///
+/// An ICN with errors, or which fails to render, doesn't overwrite the last good image.
+fn watch(opts: &ExportOptions, renderer: &mut Renderer, icon_path: &Path) -> ! {
+    const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+    let Some(save_path) = opts
+        .save
+        .as_deref()
+        .map(|save| output_path(opts.format, save, Some(icon_path), false))
+    else {
+        abort(&FailureError::WatchWithoutPaths, EXIT_CODE_INVALID_OPTIONS)
+    };
+
+    let mut last_modified = None;
+
+    loop {
+        // the file may briefly disappear, while an editor replaces it
+        let modified = std::fs::metadata(icon_path)
+            .and_then(|meta| meta.modified())
+            .ok();
+
+        if modified.is_some() && modified != last_modified {
+            last_modified = modified;
+
+            if io::stderr().is_terminal() {
+                // clear the screen, so only the latest diagnostics are shown
+                eprint!("\x1b[2J\x1b[H");
+            }
+
+            match std::fs::read_to_string(icon_path) {
+                Ok(icon_src) => {
+                    let exported =
+                        export_src(opts, renderer, Some(icon_path), &icon_src, Some(&save_path))
+                            .inspect_err(|err| {
+                                eprintln!(
+                                    "{} {}: {err}",
+                                    Color::Red.paint("Failed to render"),
+                                    icon_path.display()
+                                );
+                            })
+                            .is_ok_and(|errors| errors == 0);
+
+                    if exported {
+                        eprintln!(
+                            "{} {}",
+                            Color::Green.paint("Exported to"),
+                            save_path.display()
+                        );
+                    } else {
+                        eprintln!(
+                            "{} {}",
+                            Color::Yellow.paint("Kept the last good image at"),
+                            save_path.display()
+                        );
+                    }
+                }
+                Err(err) => eprintln!(
+                    "{} {}: {err}",
+                    Color::Red.paint("Failed to read"),
+                    icon_path.display()
+                ),
+            }
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Process, render & save the ICN source, returning the number of its errors
+///
+/// While watching, an ICN with errors is not saved.
+///
+/// # Errors
+///
+/// If the ICN can't be rendered onto the chosen canvas, in which case nothing is saved.
+fn export_src(
+    opts: &ExportOptions,
+    renderer: &mut Renderer,
+    icon_path: Option<&Path>,
+    icon_src: &str,
+    save_path: Option<&Path>,
+) -> Result<usize, RenderError> {
+    let ExportOptions {
         help: _,
-        icon: icon_path,
-        save: save_path,
+        icon: _,
+        save: _,
+        format,
+        watch,
         fit,
         pad,
         width,
@@ -37,64 +329,163 @@ pub fn export(
         camera_y,
         scale,
         background,
+        anti_aliasing: _,
+        pixel_budget: _,
         error_abort,
+        allow,
+        deny,
+        diagnostics_format,
+        comments,
+        compat,
         perf_process,
         perf_render,
         ast,
         hir,
         lir,
+        dump_format,
+        dump_to,
         chosen_sizes,
-    }: ExportOptions,
-) {
-    let icon_src = read(icon_path.as_deref());
-    let (icon, errors) = process(&icon_src, perf_process, (ast, hir, lir));
+    } = opts;
 
-    if !errors.is_empty() {
-        display_diagnostics(icon_path.as_deref(), &icon_src, &errors);
+    let mut lints = LintConfig::default();
+    for &lint in allow {
+        lints.allow(lint);
+    }
+    for &lint in deny {
+        lints.deny(lint);
+    }
+
+    let (icon, errors) = process(
+        icon_path,
+        icon_src,
+        Dialect {
+            comments: *comments,
+        },
+        *compat,
+        &lints,
+        *perf_process,
+        (*ast, *hir, *lir),
+        *dump_format,
+        dump_to.as_deref(),
+    );
+
+    // tools reading JSON / SARIF expect a report even when there's nothing in it
+    if *diagnostics_format != DiagnosticsFormat::Human
+        || !errors.is_empty()
+        || errors.warnings_len() > 0
+    {
+        display_diagnostics(icon_path, icon_src, &errors, *diagnostics_format);
     }
 
-    if !errors.is_empty() && error_abort {
-        std::process::exit(EXIT_CODE_FOUND_ERRORS);
+    if !errors.is_empty() {
+        if *watch {
+            return Ok(errors.len());
+        }
+
+        if *error_abort {
+            std::process::exit(EXIT_CODE_FOUND_ERRORS);
+        }
     }
 
     let (canvas, camera) = choose_canvas_camera(
         &icon,
-        fit,
-        pad,
+        *fit,
+        *pad,
         width.map(|w| Vector {
             x: w,
             y: height.unwrap_or(w),
         }),
-        camera_x,
-        camera_y,
-        chosen_sizes,
+        *camera_x,
+        *camera_y,
+        *chosen_sizes,
     );
 
-    let (image, image_size) = render(&icon, canvas, scale, camera, background, perf_render);
-    save(save_path.as_deref(), &image, image_size);
-
-    if !errors.is_empty() {
-        std::process::exit(EXIT_CODE_FOUND_ERRORS)
+    let format = format
+        .or_else(|| save_path.and_then(ExportFormat::from_path))
+        .unwrap_or(ExportFormat::Pam);
+
+    match format {
+        ExportFormat::Pam | ExportFormat::Png => {
+            let (image, image_size) = render(renderer, &icon, canvas, camera, *perf_render)?;
+
+            if format == ExportFormat::Png {
+                save_png(save_path, &image, image_size);
+            } else {
+                save_pam(save_path, &image, image_size);
+            }
+        }
+        ExportFormat::Svg => {
+            let image = render_svg(&icon, canvas, *scale, camera, *background, *perf_render);
+            save_svg(save_path, &image);
+        }
     }
+
+    Ok(errors.len())
 }
 
-fn process(src: &str, print_perf: bool, print_debug: (bool, bool, bool)) -> (lir::IconLir, Errors) {
+#[expect(
+    clippy::too_many_arguments,
+    reason = "the export options processing needs, unpacked"
+)]
+fn process(
+    path: Option<&Path>,
+    src: &str,
+    dialect: Dialect,
+    compat: Compat,
+    lints: &LintConfig,
+    print_perf: bool,
+    print_debug: (bool, bool, bool),
+    dump_format: DumpFormat,
+    dump_to: Option<&Path>,
+) -> (lir::IconLir, Errors) {
     let start = Instant::now();
 
-    let (icon_ast, icon_high_ir, icon_low_ir, errors) = rotur_icn_pipeline::process(src);
+    let (icon_ast, icon_high_ir, icon_low_ir, errors) =
+        rotur_icn_pipeline::process_with(src, dialect, compat, lints);
 
     let end = Instant::now();
 
-    if print_debug.0 {
-        eprintln!("--- AST ---\n{icon_ast}");
-    }
-
-    if print_debug.1 {
-        eprintln!("--- HIR ---\n{icon_high_ir}");
+    let mut dump = String::new();
+
+    match dump_format {
+        DumpFormat::Display => {
+            if print_debug.0 {
+                writeln!(dump, "--- AST ---\n{icon_ast}")
+                    .expect("writing to a string doesn\'t fail");
+            }
+
+            if print_debug.1 {
+                writeln!(dump, "--- HIR ---\n{icon_high_ir}")
+                    .expect("writing to a string doesn\'t fail");
+            }
+
+            if print_debug.2 {
+                writeln!(dump, "--- LIR ---\n{icon_low_ir}")
+                    .expect("writing to a string doesn\'t fail");
+            }
+        }
+        DumpFormat::Json if print_debug != (false, false, false) => {
+            let mut json = serde_json::json!({ "file": path.map(|p| p.to_string_lossy()) });
+
+            if print_debug.0 {
+                json["ast"] = serde_json::json!(icon_ast);
+            }
+
+            if print_debug.1 {
+                json["hir"] = serde_json::json!(icon_high_ir);
+            }
+
+            if print_debug.2 {
+                json["lir"] = serde_json::json!(icon_low_ir);
+            }
+
+            writeln!(dump, "{json}").expect("writing to a string doesn\'t fail");
+        }
+        DumpFormat::Json => {}
     }
 
-    if print_debug.2 {
-        eprintln!("--- LIR ---\n{icon_low_ir}");
+    if !dump.is_empty() {
+        write_dump(dump_to, &dump);
     }
 
     if print_perf {
@@ -108,6 +499,20 @@ fn process(src: &str, print_perf: bool, print_debug: (bool, bool, bool)) -> (lir
     (icon_low_ir, errors)
 }
 
+/// Append the dump to its file, or print it to stderr without one
+fn write_dump(path: Option<&Path>, dump: &str) {
+    let Some(path) = path else {
+        eprint!("{dump}");
+        return;
+    };
+
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(dump.as_bytes()))
+        .unwrap_or_else(|err| abort(&FailureError::WriteFile(err), EXIT_CODE_FAILED_WRITE_FILE));
+}
+
 /// Choose canvas and camera settings based on other settings.
 ///
 /// ## Canvas
@@ -163,20 +568,20 @@ fn choose_canvas_camera(
 }
 
 fn render(
+    renderer: &mut Renderer,
     icon: &lir::IconLir,
     canvas: Vector,
-    scale: f32,
     camera: Vector,
-    background: Colour,
     print_perf: bool,
-) -> (Vec<u8>, (usize, usize)) {
-    let mut renderer = Renderer::new(canvas, scale, camera, background);
+) -> Result<(Vec<u8>, (usize, usize)), RenderError> {
+    renderer.canvas = canvas;
+    renderer.camera_pos = camera;
     renderer.load(icon);
 
-    let (mut buf, buf_size) = renderer.new_buf();
+    let (mut buf, buf_size) = renderer.try_new_buf()?;
 
     let start = Instant::now();
-    renderer.render(&mut buf);
+    renderer.try_render(&mut buf)?;
     let end = Instant::now();
 
     if print_perf {
@@ -187,10 +592,59 @@ fn render(
         );
     }
 
-    (buf, buf_size)
+    Ok((buf, buf_size))
 }
 
-fn display_diagnostics(file: Option<&Path>, src: &str, errors: &Errors) {
+fn render_svg(
+    icon: &lir::IconLir,
+    canvas: Vector,
+    scale: f32,
+    camera: Vector,
+    background: Colour,
+    print_perf: bool,
+) -> String {
+    let renderer = svg::Renderer::new(canvas, scale, camera, background);
+
+    let start = Instant::now();
+    let image = renderer.render(icon);
+    let end = Instant::now();
+
+    if print_perf {
+        let perf = end - start;
+        eprintln!(
+            "Time taken to render the ICN: {:.3}ms",
+            perf.as_secs_f64() * 1e3
+        );
+    }
+
+    image
+}
+
+/// Print the errors to stderr, either for people or as a single line of JSON / SARIF
+pub fn display_diagnostics(
+    file: Option<&Path>,
+    src: &str,
+    errors: &Errors,
+    format: DiagnosticsFormat,
+) {
+    let path = file.map(|p| p.to_string_lossy());
+
+    match format {
+        DiagnosticsFormat::Human => {}
+        DiagnosticsFormat::Json => {
+            let report = serde_json::json!({
+                "file": path,
+                "diagnostics": errors.to_json(src),
+            });
+            eprintln!("{report}");
+            return;
+        }
+        DiagnosticsFormat::Sarif => {
+            eprintln!("{}", errors.to_sarif(src, file));
+            return;
+        }
+    }
+
     let file = SimpleFile::new(
         file.map_or("<stdin>".into(), |p| {
             p.file_name().unwrap().to_string_lossy()
@@ -212,14 +666,16 @@ fn display_diagnostics(file: Option<&Path>, src: &str, errors: &Errors) {
     }
 
     eprintln!(
-        "{} {} {}",
+        "{} {} {} {} {}",
         Color::Red.paint("Found"),
         Style::new().bold().paint(errors.len().to_string()),
-        Color::Red.paint("errors"),
+        Color::Red.paint("errors,"),
+        Style::new().bold().paint(errors.warnings_len().to_string()),
+        Color::Yellow.paint("warnings"),
     );
 }
 
-fn read(path: Option<&Path>) -> String {
+pub fn read(path: Option<&Path>) -> String {
     if let Some(path) = path {
         io::read_to_string(
             std::fs::File::open(path).unwrap_or_else(|err| {
@@ -232,8 +688,8 @@ fn read(path: Option<&Path>) -> String {
     .unwrap_or_else(|err| abort(&FailureError::ReadFile(err), EXIT_CODE_FAILED_READ_FILE))
 }
 
-fn save(path: Option<&Path>, buf: &[u8], buf_size: (usize, usize)) {
-    let mut writer = if let Some(file) = path {
+fn open_save(path: Option<&Path>) -> Box<dyn Write> {
+    if let Some(file) = path {
         Box::new(
             std::fs::OpenOptions::new()
                 .write(true)
@@ -246,7 +702,11 @@ fn save(path: Option<&Path>, buf: &[u8], buf_size: (usize, usize)) {
         ) as Box<dyn Write>
     } else {
         Box::new(std::io::stdout()) as Box<dyn Write>
-    };
+    }
+}
+
+fn save_pam(path: Option<&Path>, buf: &[u8], buf_size: (usize, usize)) {
+    let mut writer = open_save(path);
 
     write!(
         writer,
@@ -265,3 +725,29 @@ fn save(path: Option<&Path>, buf: &[u8], buf_size: (usize, usize)) {
         .write_all(buf)
         .unwrap_or_else(|err| abort(&FailureError::WriteFile(err), EXIT_CODE_FAILED_WRITE_FILE));
 }
+
+fn save_png(path: Option<&Path>, buf: &[u8], buf_size: (usize, usize)) {
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "PNG is limited to u32 sizes"
+    )]
+    let mut encoder = png::Encoder::new(open_save(path), buf_size.0 as u32, buf_size.1 as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(buf))
+        .unwrap_or_else(|err| {
+            abort(
+                &FailureError::EncodeImage(err),
+                EXIT_CODE_FAILED_ENCODE_IMAGE,
+            )
+        });
+}
+
+fn save_svg(path: Option<&Path>, image: &str) {
+    open_save(path)
+        .write_all(image.as_bytes())
+        .unwrap_or_else(|err| abort(&FailureError::WriteFile(err), EXIT_CODE_FAILED_WRITE_FILE));
+}
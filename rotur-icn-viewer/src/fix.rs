@@ -0,0 +1,115 @@
+use std::path::Path;
+
+use ansi_term::{Color, Style};
+use rotur_icn_pipeline::{Compat, Dialect, LintConfig, apply_suggestions};
+
+use crate::{
+    abort::abort,
+    error::{EXIT_CODE_FAILED_WRITE_FILE, EXIT_CODE_FOUND_ERRORS, FailureError},
+    export::{display_diagnostics, expand_globs, read},
+    options::{DiagnosticsFormat, FixOptions},
+};
+
+/// How many times the ICN is processed again, as fixes may reveal further errors
+const MAX_PASSES: usize = 8;
+
+pub fn fix(
+    FixOptions {
+        help: _,
+        icon: icon_paths,
+        comments,
+        compat,
+        diagnostics_format,
+    }: &FixOptions,
+) {
+    let dialect = Dialect {
+        comments: *comments,
+    };
+
+    if icon_paths.is_empty() {
+        let icon_src = read(None);
+
+        let (fixed, _, errors_left) =
+            fix_src(None, &icon_src, dialect, *compat, *diagnostics_format);
+        print!("{fixed}");
+
+        if errors_left {
+            std::process::exit(EXIT_CODE_FOUND_ERRORS)
+        }
+
+        return;
+    }
+
+    let mut failed = 0;
+
+    for icon_path in expand_globs(icon_paths) {
+        let icon_src = read(Some(&icon_path));
+
+        let (fixed, applied, errors_left) = fix_src(
+            Some(&icon_path),
+            &icon_src,
+            dialect,
+            *compat,
+            *diagnostics_format,
+        );
+
+        if errors_left {
+            failed += 1;
+        }
+
+        if applied == 0 {
+            continue;
+        }
+
+        std::fs::write(&icon_path, fixed).unwrap_or_else(|err| {
+            abort(&FailureError::WriteFile(err), EXIT_CODE_FAILED_WRITE_FILE)
+        });
+
+        eprintln!(
+            "{} {} {} {}",
+            Color::Green.paint("Applied"),
+            Style::new().bold().paint(applied.to_string()),
+            Color::Green.paint("fixes to"),
+            Style::new().bold().paint(icon_path.display().to_string()),
+        );
+    }
+
+    if failed > 0 {
+        std::process::exit(EXIT_CODE_FOUND_ERRORS)
+    }
+}
+
+/// Apply the suggested fixes, until there are none left
+///
+/// Returns the fixed source, the number of the applied fixes
+/// & whether any errors are left, which are then displayed.
+fn fix_src(
+    path: Option<&Path>,
+    src: &str,
+    dialect: Dialect,
+    compat: Compat,
+    diagnostics_format: DiagnosticsFormat,
+) -> (String, usize, bool) {
+    let mut src = src.to_owned();
+    let mut applied = 0;
+    let mut passes = 0;
+
+    loop {
+        let (_, _, _, errors) =
+            rotur_icn_pipeline::process_with(&src, dialect, compat, &LintConfig::default());
+        let suggestions = errors.suggestions();
+
+        if suggestions.is_empty() || passes == MAX_PASSES {
+            if diagnostics_format != DiagnosticsFormat::Human || !errors.is_empty() {
+                display_diagnostics(path, &src, &errors, diagnostics_format);
+            }
+
+            return (src, applied, !errors.is_empty());
+        }
+
+        let (fixed, applied_now) = apply_suggestions(&src, &suggestions);
+        src = fixed;
+        applied += applied_now;
+        passes += 1;
+    }
+}
@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use ansi_term::{Color, Style as TermStyle};
+use rotur_icn_pipeline::{Compat, Dialect, LintConfig};
+use rotur_icn_printer::{Style, convert, stringify_styled};
+
+use crate::{
+    abort::abort,
+    error::{
+        EXIT_CODE_FAILED_WRITE_FILE, EXIT_CODE_FOUND_ERRORS, EXIT_CODE_NOT_FORMATTED, FailureError,
+    },
+    export::{display_diagnostics, expand_globs, read},
+    options::{DiagnosticsFormat, FmtOptions},
+};
+
+pub fn format(
+    FmtOptions {
+        help: _,
+        icon: icon_paths,
+        check,
+        layout,
+        precision,
+        short_colours,
+        comments,
+        compat,
+        diagnostics_format,
+        optimise,
+    }: &FmtOptions,
+) {
+    let style = Style {
+        layout: *layout,
+        precision: *precision,
+        short_colours: *short_colours,
+    };
+    let dialect = Dialect {
+        comments: *comments,
+    };
+
+    if icon_paths.is_empty() {
+        let icon_src = read(None);
+
+        let Some(formatted) = format_src(
+            None,
+            &icon_src,
+            &style,
+            dialect,
+            *compat,
+            *optimise,
+            *diagnostics_format,
+        ) else {
+            std::process::exit(EXIT_CODE_FOUND_ERRORS)
+        };
+
+        if !check {
+            print!("{formatted}");
+        } else if formatted != icon_src {
+            eprintln!("{}", Color::Yellow.paint("The ICN is not formatted"));
+            std::process::exit(EXIT_CODE_NOT_FORMATTED)
+        }
+
+        return;
+    }
+
+    let mut failed = 0;
+    let mut unformatted = 0;
+
+    for icon_path in expand_globs(icon_paths) {
+        let icon_src = read(Some(&icon_path));
+
+        let Some(formatted) = format_src(
+            Some(&icon_path),
+            &icon_src,
+            &style,
+            dialect,
+            *compat,
+            *optimise,
+            *diagnostics_format,
+        ) else {
+            failed += 1;
+            continue;
+        };
+
+        if formatted == icon_src {
+            continue;
+        }
+
+        if *check {
+            eprintln!(
+                "{} {}",
+                Color::Yellow.paint("Not formatted:"),
+                TermStyle::new()
+                    .bold()
+                    .paint(icon_path.display().to_string())
+            );
+            unformatted += 1;
+        } else {
+            std::fs::write(&icon_path, formatted).unwrap_or_else(|err| {
+                abort(&FailureError::WriteFile(err), EXIT_CODE_FAILED_WRITE_FILE)
+            });
+        }
+    }
+
+    if failed > 0 {
+        std::process::exit(EXIT_CODE_FOUND_ERRORS)
+    }
+
+    if unformatted > 0 {
+        std::process::exit(EXIT_CODE_NOT_FORMATTED)
+    }
+}
+
+/// Reprint the ICN source in the style
+///
+/// An ICN which failed to lex or parse is left alone, as reprinting it would lose the broken parts.
+/// Optimising needs an ICN without any errors, as it's reprinted from its HIR, losing comments.
+fn format_src(
+    path: Option<&Path>,
+    src: &str,
+    style: &Style,
+    dialect: Dialect,
+    compat: Compat,
+    optimise: bool,
+    diagnostics_format: DiagnosticsFormat,
+) -> Option<String> {
+    let (icon, icon_high_ir, _, errors) =
+        rotur_icn_pipeline::process_with(src, dialect, compat, &LintConfig::default());
+
+    if !errors.lexing.is_empty() || !errors.parsing.is_empty() || (optimise && !errors.is_empty()) {
+        display_diagnostics(path, src, &errors, diagnostics_format);
+        return None;
+    }
+
+    if optimise {
+        let optimised = rotur_icn_optimiser::optimise_with(&icon_high_ir, compat);
+        Some(stringify_styled(&convert(&optimised), style))
+    } else {
+        Some(stringify_styled(&icon, style))
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use crate::{
+    abort::abort,
+    error::{EXIT_CODE_FAILED_RUN_GUI, FailureError},
+    export::read,
+    options::GuiOptions,
+};
+
+use self::view::View;
+
+mod app;
+mod view;
+
+pub fn gui(
+    GuiOptions {
+        help: _,
+        icon: icon_path,
+        scale,
+        background,
+        anti_aliasing,
+        compat,
+    }: GuiOptions,
+) {
+    let icon_src = read(icon_path.as_deref());
+
+    let mut view = View::new(background, anti_aliasing);
+    view.compat = compat;
+    view.load(&file_name(icon_path.as_deref()), &icon_src);
+
+    if let Some(scale) = scale {
+        view.set_scaling(scale);
+    }
+
+    let title = format!("{} - rotur ICN viewer", file_name(icon_path.as_deref()));
+    let options = eframe::NativeOptions {
+        viewport: eframe::egui::ViewportBuilder::default()
+            .with_title(&title)
+            .with_inner_size([960., 640.]),
+        ..Default::default()
+    };
+
+    let app = app::App::new(view, icon_path, scale.is_none());
+
+    eframe::run_native(&title, options, Box::new(|_| Ok(Box::new(app))))
+        .unwrap_or_else(|err| abort(&FailureError::RunGui(err), EXIT_CODE_FAILED_RUN_GUI));
+}
+
+fn file_name(path: Option<&Path>) -> String {
+    // paths like `..` or `/` have no file name
+    path.map_or("<stdin>".into(), |p| {
+        p.file_name().map_or_else(
+            || p.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    })
+}
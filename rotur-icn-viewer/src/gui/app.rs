@@ -0,0 +1,186 @@
+use std::path::PathBuf;
+
+use eframe::egui::{self, Color32, ColorImage, Rect, Sense, TextureHandle, TextureOptions, pos2};
+use rotur_icn_units::{Number, Vector};
+
+use super::{View, file_name};
+
+/// Scroll distance, in points, which zooms the view by a factor of `e`
+const SCROLL_ZOOM_SPEED: f32 = 200.;
+
+pub struct App {
+    view: View,
+    icon_path: Option<PathBuf>,
+    texture: Option<TextureHandle>,
+    /// Size of the last rendered frame, in physical pixels
+    frame_size: (usize, usize),
+    /// The view changed since the last render
+    dirty: bool,
+    /// Fit the view to the icon at the next frame
+    fit: bool,
+}
+
+impl App {
+    pub fn new(view: View, icon_path: Option<PathBuf>, fit: bool) -> Self {
+        Self {
+            view,
+            icon_path,
+            texture: None,
+            frame_size: (0, 0),
+            dirty: true,
+            fit,
+        }
+    }
+
+    fn reload(&mut self) {
+        let Some(path) = &self.icon_path else {
+            return;
+        };
+
+        match std::fs::read_to_string(path) {
+            Ok(src) => self.view.load(&file_name(Some(path)), &src),
+            Err(err) => self
+                .view
+                .set_message(format!("failed to read {}: {err}", path.display())),
+        }
+
+        self.dirty = true;
+    }
+
+    fn side_panel(&mut self, ui: &mut egui::Ui) {
+        ui.heading(file_name(self.icon_path.as_deref()));
+
+        ui.horizontal(|ui| {
+            if ui.button("Fit").clicked() {
+                self.fit = true;
+            }
+
+            if ui
+                .add_enabled(self.icon_path.is_some(), egui::Button::new("Reload"))
+                .clicked()
+            {
+                self.reload();
+            }
+        });
+
+        if ui
+            .checkbox(&mut self.view.renderer.anti_aliasing, "Anti-aliasing")
+            .changed()
+        {
+            self.dirty = true;
+        }
+
+        ui.label(format!(
+            "Scale: {:.2}, camera: {}",
+            self.view.scaling(),
+            self.view.renderer.camera_pos
+        ));
+
+        ui.separator();
+
+        if self.view.errors == 0 {
+            ui.label("No errors");
+        } else {
+            ui.colored_label(Color32::RED, format!("Found {} errors", self.view.errors));
+        }
+
+        egui::ScrollArea::both().show(ui, |ui| {
+            ui.monospace(&self.view.diagnostics);
+        });
+    }
+
+    fn canvas(&mut self, ui: &mut egui::Ui) {
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+        let pixels_per_point = ui.pixels_per_point();
+
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "the panel's size is small & positive"
+        )]
+        let frame_size = (
+            (rect.width() * pixels_per_point).round() as usize,
+            (rect.height() * pixels_per_point).round() as usize,
+        );
+
+        if response.double_clicked() {
+            self.fit = true;
+        }
+
+        if self.fit {
+            self.view.fit(frame_size);
+            self.fit = false;
+            self.dirty = true;
+        }
+
+        if response.dragged() {
+            let delta = response.drag_delta() * pixels_per_point;
+            self.view.pan(Vector {
+                x: delta.x,
+                y: delta.y,
+            });
+            self.dirty = true;
+        }
+
+        if let Some(hover) = response.hover_pos() {
+            let (scroll, zoom) = ui.input(|i| (i.smooth_scroll_delta.y, i.zoom_delta()));
+            let factor: Number = (scroll / SCROLL_ZOOM_SPEED).exp() * zoom;
+
+            if (factor - 1.).abs() > Number::EPSILON {
+                let anchor = (hover - rect.center()) * pixels_per_point;
+                self.view.zoom(
+                    factor,
+                    Vector {
+                        x: anchor.x,
+                        y: anchor.y,
+                    },
+                );
+                self.dirty = true;
+            }
+        }
+
+        if self.dirty || frame_size != self.frame_size {
+            match self.view.render_frame(frame_size) {
+                Ok((buf, buf_size)) => {
+                    let image = ColorImage::from_rgba_unmultiplied([buf_size.0, buf_size.1], &buf);
+
+                    match &mut self.texture {
+                        Some(texture) => texture.set(image, TextureOptions::NEAREST),
+                        None => {
+                            self.texture = Some(ui.ctx().load_texture(
+                                "icon",
+                                image,
+                                TextureOptions::NEAREST,
+                            ));
+                        }
+                    }
+                }
+                // the last frame is kept, so the view can be zoomed back out
+                Err(err) => self.view.set_message(format!("failed to render: {err}")),
+            }
+
+            self.frame_size = frame_size;
+            self.dirty = false;
+        }
+
+        if let Some(texture) = &self.texture {
+            ui.painter().image(
+                texture.id(),
+                rect,
+                Rect::from_min_max(pos2(0., 0.), pos2(1., 1.)),
+                Color32::WHITE,
+            );
+        }
+    }
+}
+
+impl eframe::App for App {
+    fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
+        egui::Panel::right("diagnostics")
+            .resizable(true)
+            .default_size(320.)
+            .show(ui, |ui| self.side_panel(ui));
+
+        egui::CentralPanel::default().show(ui, |ui| self.canvas(ui));
+    }
+}
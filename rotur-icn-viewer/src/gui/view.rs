@@ -0,0 +1,261 @@
+use codespan_reporting::files::SimpleFile;
+use rotur_icn_pipeline::{Compat, Dialect, LintConfig};
+use rotur_icn_renderer::{
+    cpu::{RenderError, Renderer},
+    fitter,
+};
+use rotur_icn_resolver::lir;
+use rotur_icn_units::{Colour, Number, Vector};
+
+const MIN_SCALING: Number = 0.05;
+const MAX_SCALING: Number = 2000.;
+/// Part of the frame the icon fills, when fitting, leaving the rest as a margin
+const FIT_MARGIN: Number = 0.9;
+
+/// Display-independent state of the viewer: the loaded ICN, its diagnostics & the camera
+///
+/// Frames are rendered into plain RGBA buffers, so this works without any window.
+pub struct View {
+    pub renderer: Renderer,
+    icon: lir::IconLir,
+    /// Pipeline diagnostics of the loaded ICN, as plain text
+    pub diagnostics: String,
+    pub errors: usize,
+    /// Semantics the ICN is processed with
+    pub compat: Compat,
+}
+
+impl View {
+    pub fn new(background: Colour, anti_aliasing: bool) -> Self {
+        let mut renderer = Renderer::new(Vector::ZERO, 1., Vector::ZERO, background);
+        renderer.anti_aliasing = anti_aliasing;
+
+        let icon = lir::IconLir {
+            elements: Vec::new(),
+        };
+        renderer.load(&icon);
+
+        Self {
+            renderer,
+            icon,
+            diagnostics: String::new(),
+            errors: 0,
+            compat: Compat::default(),
+        }
+    }
+
+    /// Process the ICN source & show it, keeping the camera as is
+    pub fn load(&mut self, name: &str, src: &str) {
+        let (_, _, icon, errors) = rotur_icn_pipeline::process_with(
+            src,
+            Dialect::default(),
+            self.compat,
+            &LintConfig::default(),
+        );
+
+        let file = SimpleFile::new(name, src);
+        let config = codespan_reporting::term::Config::default();
+
+        self.diagnostics.clear();
+        for diag in errors.into_diagnostics() {
+            if codespan_reporting::term::emit_to_string(
+                &mut self.diagnostics,
+                &config,
+                &file,
+                &diag,
+            )
+            .is_err()
+            {
+                self.diagnostics
+                    .push_str("<failed to display a diagnostic>\n");
+            }
+        }
+
+        self.errors = errors.len();
+        self.renderer.load(&icon);
+        self.icon = icon;
+    }
+
+    /// Show a message instead of the diagnostics, e.g. when the ICN couldn't be read
+    pub fn set_message(&mut self, message: String) {
+        self.diagnostics = message;
+    }
+
+    /// Centre the camera on the icon & scale it to fill the frame
+    #[expect(clippy::cast_precision_loss)]
+    pub fn fit(&mut self, frame_size: (usize, usize)) {
+        let fitted = fitter::fit(&self.icon);
+        self.renderer.camera_pos = fitted.camera;
+
+        let frame = Vector {
+            x: frame_size.0 as Number,
+            y: frame_size.1 as Number,
+        };
+        let scaling = (frame / fitted.size).min_axis() * FIT_MARGIN;
+
+        // an empty or a point-like icon has nothing to fit to
+        if scaling.is_finite() && scaling > 0. {
+            self.set_scaling(scaling);
+        }
+    }
+
+    /// Move the camera by a drag of the frame's pixels (Y pointing down)
+    pub fn pan(&mut self, delta: Vector) {
+        self.renderer.camera_pos += -delta.conj() / self.renderer.scaling;
+    }
+
+    /// Zoom by a factor, keeping the point under the anchor in place
+    ///
+    /// The anchor is in the frame's pixels, relative to its centre (Y pointing down).
+    pub fn zoom(&mut self, factor: Number, anchor: Vector) {
+        let anchor = anchor.conj();
+        let world = anchor / self.renderer.scaling + self.renderer.camera_pos;
+
+        self.set_scaling(self.renderer.scaling * factor);
+
+        self.renderer.camera_pos = world - anchor / self.renderer.scaling;
+    }
+
+    pub fn scaling(&self) -> Number {
+        self.renderer.scaling
+    }
+
+    pub fn set_scaling(&mut self, scaling: Number) {
+        self.renderer.scaling = scaling.clamp(MIN_SCALING, MAX_SCALING);
+    }
+
+    /// Render the current view into a new RGBA buffer of the frame's size
+    ///
+    /// # Errors
+    ///
+    /// If the frame is too large to render at the current scaling.
+    #[expect(clippy::cast_precision_loss)]
+    pub fn render_frame(
+        &mut self,
+        frame_size: (usize, usize),
+    ) -> Result<(Vec<u8>, (usize, usize)), RenderError> {
+        self.renderer.canvas = Vector {
+            x: frame_size.0 as Number,
+            y: frame_size.1 as Number,
+        } / self.renderer.scaling;
+
+        let (mut buf, buf_size) = self.renderer.try_new_buf()?;
+        self.renderer.try_render(&mut buf)?;
+
+        Ok((buf, buf_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rotur_icn_renderer::cpu::RenderError;
+    use rotur_icn_units::{Colour, Number, Vector};
+
+    use super::{MAX_SCALING, View};
+
+    fn view_of(src: &str) -> View {
+        let mut view = View::new(Colour::ZERO, false);
+        view.load("test.icn", src);
+        view
+    }
+
+    /// Columns of the frame's middle row, which aren't the background
+    fn covered_columns(buf: &[u8], frame_size: (usize, usize)) -> Vec<usize> {
+        let row = frame_size.1 / 2;
+
+        (0..frame_size.0)
+            .filter(|x| buf[(row * frame_size.0 + x) * 4 + 3] != 0)
+            .collect()
+    }
+
+    fn world_under(view: &View, anchor: Vector) -> Vector {
+        anchor.conj() / view.scaling() + view.renderer.camera_pos
+    }
+
+    #[test]
+    fn render_frame_fills_the_frame() {
+        let mut view = view_of("rect 0 0 6 4");
+
+        let (buf, buf_size) = view.render_frame((40, 30)).expect("the frame renders");
+
+        assert_eq!(buf_size, (40, 30));
+        assert_eq!(buf.len(), 40 * 30 * 4);
+        assert_eq!(buf[(15 * 40 + 20) * 4..][..4], [255; 4]);
+        assert_eq!(buf[..4], [0; 4]);
+    }
+
+    #[test]
+    fn render_frame_of_nothing_is_background() {
+        let mut view = view_of("");
+
+        let (buf, _) = view.render_frame((8, 8)).expect("the frame renders");
+
+        assert!(buf.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn render_frame_over_the_budget_fails() {
+        let mut view = view_of("dot 0 0");
+        view.renderer.pixel_budget = 63;
+
+        assert!(matches!(
+            view.render_frame((8, 8)),
+            Err(RenderError::CanvasTooLarge {
+                width: 8,
+                height: 8,
+                budget: 63,
+            })
+        ));
+    }
+
+    #[test]
+    fn zoom_keeps_the_anchor_in_place() {
+        let mut view = view_of("dot 0 0");
+        view.set_scaling(2.);
+
+        let anchor = Vector { x: 10., y: -4. };
+        let before = world_under(&view, anchor);
+
+        view.zoom(3., anchor);
+
+        assert!((view.scaling() - 6.).abs() < 1e-4);
+        assert!((world_under(&view, anchor) - before).length() < 1e-4);
+    }
+
+    #[test]
+    fn zoom_is_clamped() {
+        let mut view = view_of("dot 0 0");
+
+        view.zoom(1e9, Vector::ZERO);
+
+        assert!((view.scaling() - MAX_SCALING).abs() < 1e-4);
+    }
+
+    #[test]
+    fn fit_centres_the_icon_filling_the_frame() {
+        let mut view = view_of("rect 20 10 6 4");
+        let frame_size = (100, 100);
+
+        view.fit(frame_size);
+        let (buf, _) = view.render_frame(frame_size).expect("the frame renders");
+
+        let covered = covered_columns(&buf, frame_size);
+        let (first, last) = (covered[0], covered[covered.len() - 1]);
+
+        #[expect(clippy::cast_precision_loss)]
+        let (centre, width) = ((first + last) as Number / 2., (last - first + 1) as Number);
+
+        assert!((centre - 49.5).abs() <= 1., "centred at {centre}");
+        assert!((width - 90.).abs() <= 2., "{width} columns wide");
+    }
+
+    #[test]
+    fn fit_keeps_the_scaling_of_nothing() {
+        let mut view = view_of("");
+        view.set_scaling(4.);
+
+        view.fit((100, 100));
+
+        assert!((view.scaling() - 4.).abs() < 1e-4);
+    }
+}
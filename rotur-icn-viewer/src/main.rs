@@ -2,18 +2,30 @@ use gumdrop::Options as _;
 
 use options::ViewerOptions;
 
-use crate::{export::export, options::ViewerMode};
+use crate::{export::export, fix::fix, format::format, options::ViewerMode};
 
 mod abort;
 mod error;
 mod export;
+mod fix;
+mod format;
+#[cfg(feature = "gui")]
+mod gui;
 mod options;
 
 fn main() {
     let opts = ViewerOptions::parse_args_default_or_exit();
 
     match opts.mode() {
-        ViewerMode::Gui(_) => todo!("GUI will not be ready soon"),
-        ViewerMode::Export(opts) => export(opts),
+        #[cfg(feature = "gui")]
+        ViewerMode::Gui(opts) => gui::gui(opts),
+        #[cfg(not(feature = "gui"))]
+        ViewerMode::Gui(_) => abort::abort(
+            &error::FailureError::GuiUnavailable,
+            error::EXIT_CODE_FAILED_RUN_GUI,
+        ),
+        ViewerMode::Export(opts) => export(&opts),
+        ViewerMode::Fmt(opts) => format(&opts),
+        ViewerMode::Fix(opts) => fix(&opts),
     }
 }
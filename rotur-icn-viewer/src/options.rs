@@ -1,7 +1,14 @@
-use std::{num::ParseIntError, path::PathBuf};
+use std::{
+    fmt,
+    num::ParseIntError,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use gumdrop::Options;
 
+use rotur_icn_pipeline::{Compat, Lint};
+use rotur_icn_printer::Layout;
 use rotur_icn_units::{Colour, Number};
 
 #[derive(Debug, Clone, Options)]
@@ -23,6 +30,8 @@ impl ViewerOptions {
 pub enum ViewerMode {
     Export(ExportOptions),
     Gui(GuiOptions),
+    Fmt(FmtOptions),
+    Fix(FixOptions),
 }
 
 impl Default for ViewerMode {
@@ -40,16 +49,33 @@ pub struct ExportOptions {
     #[options(help = "print this message")]
     pub help: bool,
 
-    #[options(free, help = "path to the ICN file to render (default: stdin)")]
-    pub icon: Option<PathBuf>,
+    #[options(
+        free,
+        help = "paths or globs of the ICN files to render (default: stdin)"
+    )]
+    pub icon: Vec<PathBuf>,
 
     #[options(
         no_short,
-        help = "path to export a PAM to (default: stdout)",
+        help = "path to export the image to, `{stem}` is replaced by the ICN's name; \
+                a directory, when exporting several ICNs (default: stdout)",
         meta = "PATH"
     )]
     pub save: Option<PathBuf>,
 
+    #[options(
+        no_short,
+        help = "format of the exported image: pam, png, svg (default: from save path, or pam)",
+        meta = "FMT"
+    )]
+    pub format: Option<ExportFormat>,
+
+    #[options(
+        short = "w",
+        help = "keep re-exporting whenever the ICN file changes (needs a path & --save)"
+    )]
+    pub watch: bool,
+
     #[options(short = "F", help = "fit canvas & camera to icon's edges")]
     pub fit: bool,
 
@@ -96,9 +122,56 @@ pub struct ExportOptions {
     )]
     pub background: Colour,
 
+    #[options(
+        short = "A",
+        help = "smooth shape edges by blending partially covered pixels"
+    )]
+    pub anti_aliasing: bool,
+
+    #[options(
+        no_short,
+        help = "fail on images with more pixels (default: 67108864)",
+        meta = "PIXELS"
+    )]
+    pub pixel_budget: Option<usize>,
+
     #[options(no_short, help = "abort, if any errors in the ICN were found")]
     pub error_abort: bool,
 
+    #[options(
+        no_short,
+        help = "don't report a lint, by its name or code (e.g. zero-width or WL00)",
+        meta = "LINT"
+    )]
+    pub allow: Vec<Lint>,
+
+    #[options(
+        no_short,
+        help = "report a lint as an error, by its name or code",
+        meta = "LINT"
+    )]
+    pub deny: Vec<Lint>,
+
+    #[options(
+        no_short,
+        help = "how to print the found errors: human, json, sarif (one per ICN, on a line)",
+        meta = "FMT",
+        default = "human"
+    )]
+    pub diagnostics_format: DiagnosticsFormat,
+
+    #[options(no_short, help = "allow `//` line comments, which originOS doesn't")]
+    pub comments: bool,
+
+    #[options(
+        no_short,
+        help = "semantics, which may differ from originOS, as comma-separated KEY=VALUE: \
+                max-arm-angle, default-width, arc-direction-scale, offset-continued-lines",
+        meta = "SETTINGS",
+        parse(try_from_str = "parse_compat")
+    )]
+    pub compat: Compat,
+
     #[options(no_short, help = "print the time taken to process the ICN")]
     pub perf_process: bool,
 
@@ -114,13 +187,366 @@ pub struct ExportOptions {
     #[options(no_short, help = "print the LIR representation of the ICN")]
     pub lir: bool,
 
+    #[options(
+        no_short,
+        help = "how to print the representations: display, json (one per ICN, on a line)",
+        meta = "FMT",
+        default = "display"
+    )]
+    pub dump_format: DumpFormat,
+
+    #[options(
+        no_short,
+        help = "file to write the representations to, needed for json (default: stderr)",
+        meta = "PATH"
+    )]
+    pub dump_to: Option<PathBuf>,
+
     #[options(no_short, help = "print the final chosen canvas & camera")]
     pub chosen_sizes: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Pam,
+    Png,
+    Svg,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Pam => "pam",
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+
+    /// Choose the format by the file extension, if it's a known one
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?
+            .to_str()?
+            .to_ascii_lowercase()
+            .parse()
+            .ok()
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = UnknownFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pam" => Ok(Self::Pam),
+            "png" => Ok(Self::Png),
+            "svg" => Ok(Self::Svg),
+            _ => Err(UnknownFormatError),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnknownFormatError;
+
+impl fmt::Display for UnknownFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown image format")
+    }
+}
+
+impl std::error::Error for UnknownFormatError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpFormat {
+    Display,
+    Json,
+}
+
+impl FromStr for DumpFormat {
+    type Err = UnknownDumpFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "display" => Ok(Self::Display),
+            "json" => Ok(Self::Json),
+            _ => Err(UnknownDumpFormatError),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnknownDumpFormatError;
+
+impl fmt::Display for UnknownDumpFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown dump format")
+    }
+}
+
+impl std::error::Error for UnknownDumpFormatError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticsFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+impl FromStr for DiagnosticsFormat {
+    type Err = UnknownDiagnosticsFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            _ => Err(UnknownDiagnosticsFormatError),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnknownDiagnosticsFormatError;
+
+impl fmt::Display for UnknownDiagnosticsFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown diagnostics format")
+    }
+}
+
+impl std::error::Error for UnknownDiagnosticsFormatError {}
+
+#[derive(Debug, Clone, Options)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "not a state machine, but a flag store"
+)]
+pub struct FmtOptions {
+    #[options(help = "print this message")]
+    pub help: bool,
+
+    #[options(
+        free,
+        help = "paths or globs of the ICN files to format in place (default: stdin to stdout)"
+    )]
+    pub icon: Vec<PathBuf>,
+
+    #[options(no_short, help = "only check whether the ICNs are already formatted")]
+    pub check: bool,
+
+    #[options(
+        short = "L",
+        help = "layout of the commands: oneline, lines, grouped (by colour & width)",
+        meta = "LAYOUT",
+        parse(try_from_str = "parse_layout"),
+        default = "lines"
+    )]
+    pub layout: Layout,
+
+    #[options(short = "p", help = "round numbers to decimal places", meta = "N")]
+    pub precision: Option<usize>,
+
+    #[options(short = "s", help = "shorten colours to #rgb, when it's exact")]
+    pub short_colours: bool,
+
+    #[options(
+        no_short,
+        help = "allow & keep `//` line comments, which originOS doesn't"
+    )]
+    pub comments: bool,
+
+    #[options(
+        no_short,
+        help = "semantics, which may differ from originOS, as comma-separated KEY=VALUE: \
+                max-arm-angle, default-width, arc-direction-scale, offset-continued-lines",
+        meta = "SETTINGS",
+        parse(try_from_str = "parse_compat")
+    )]
+    pub compat: Compat,
+
+    #[options(
+        no_short,
+        help = "how to print the found errors: human, json, sarif (one per ICN, on a line)",
+        meta = "FMT",
+        default = "human"
+    )]
+    pub diagnostics_format: DiagnosticsFormat,
+
+    #[options(
+        short = "O",
+        help = "remove operations, which don't change how the ICN looks"
+    )]
+    pub optimise: bool,
+}
+
+#[derive(Debug, Clone, Options)]
+pub struct FixOptions {
+    #[options(help = "print this message")]
+    pub help: bool,
+
+    #[options(
+        free,
+        help = "paths or globs of the ICN files to fix in place (default: stdin to stdout)"
+    )]
+    pub icon: Vec<PathBuf>,
+
+    #[options(no_short, help = "allow `//` line comments, which originOS doesn't")]
+    pub comments: bool,
+
+    #[options(
+        no_short,
+        help = "semantics, which may differ from originOS, as comma-separated KEY=VALUE: \
+                max-arm-angle, default-width, arc-direction-scale, offset-continued-lines",
+        meta = "SETTINGS",
+        parse(try_from_str = "parse_compat")
+    )]
+    pub compat: Compat,
+
+    #[options(
+        no_short,
+        help = "how to print the errors left: human, json, sarif (one per ICN, on a line)",
+        meta = "FMT",
+        default = "human"
+    )]
+    pub diagnostics_format: DiagnosticsFormat,
+}
+
+fn parse_layout(s: &str) -> Result<Layout, UnknownLayoutError> {
+    match s {
+        "oneline" => Ok(Layout::Oneline),
+        "lines" => Ok(Layout::PerLine),
+        "grouped" => Ok(Layout::Grouped),
+        _ => Err(UnknownLayoutError),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnknownLayoutError;
+
+impl fmt::Display for UnknownLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown layout")
+    }
+}
+
+impl std::error::Error for UnknownLayoutError {}
+
 fn parse_colour(s: &str) -> Result<Colour, ParseIntError> {
     Ok(Colour::from_u32_with_alpha(u32::from_str_radix(s, 16)?))
 }
 
-#[derive(Debug, Clone, Default, Options)]
-pub struct GuiOptions {}
+/// The settings, which aren't listed, are left at their defaults
+fn parse_compat(s: &str) -> Result<Compat, InvalidCompatError> {
+    let mut compat = Compat::default();
+
+    for setting in s.split(',').filter(|setting| !setting.is_empty()) {
+        let (key, value) = setting.split_once('=').ok_or(InvalidCompatError)?;
+
+        match key {
+            "max-arm-angle" => compat.max_arm_angle = parse_setting(value)?,
+            "default-width" => compat.default_width = parse_setting(value)?,
+            "arc-direction-scale" => compat.arc_direction_scale = parse_setting(value)?,
+            "offset-continued-lines" => compat.offset_continued_lines = parse_setting(value)?,
+            _ => return Err(InvalidCompatError),
+        }
+    }
+
+    Ok(compat)
+}
+
+fn parse_setting<T: FromStr>(value: &str) -> Result<T, InvalidCompatError> {
+    value.parse().map_err(|_| InvalidCompatError)
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidCompatError;
+
+impl fmt::Display for InvalidCompatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid semantics settings")
+    }
+}
+
+impl std::error::Error for InvalidCompatError {}
+
+#[derive(Debug, Clone, Options)]
+pub struct GuiOptions {
+    #[options(help = "print this message")]
+    pub help: bool,
+
+    #[options(free, help = "path to the ICN file to view (default: stdin)")]
+    pub icon: Option<PathBuf>,
+
+    #[options(
+        short = "S",
+        help = "initial scale of the view (default: fit to window)"
+    )]
+    pub scale: Option<Number>,
+
+    #[options(
+        short = "C",
+        help = "set the background colour (8-char HEX, RGBA)",
+        meta = "COL",
+        parse(try_from_str = "parse_colour"),
+        default = "00000000"
+    )]
+    pub background: Colour,
+
+    #[options(
+        short = "A",
+        help = "smooth shape edges by blending partially covered pixels"
+    )]
+    pub anti_aliasing: bool,
+
+    #[options(
+        no_short,
+        help = "semantics, which may differ from originOS, as comma-separated KEY=VALUE: \
+                max-arm-angle, default-width, arc-direction-scale, offset-continued-lines",
+        meta = "SETTINGS",
+        parse(try_from_str = "parse_compat")
+    )]
+    pub compat: Compat,
+}
+
+impl Default for GuiOptions {
+    fn default() -> Self {
+        Self {
+            help: false,
+            icon: None,
+            scale: None,
+            background: Colour::ZERO,
+            anti_aliasing: false,
+            compat: Compat::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rotur_icn_pipeline::Compat;
+
+    use super::parse_compat;
+
+    #[test]
+    fn compat_keeps_unlisted_settings_at_their_defaults() {
+        let compat = parse_compat("default-width=2,offset-continued-lines=false")
+            .expect("the settings are valid");
+
+        assert_eq!(
+            compat,
+            Compat {
+                default_width: 2.,
+                offset_continued_lines: false,
+                ..Compat::default()
+            }
+        );
+        assert!(parse_compat("").is_ok_and(|compat| compat == Compat::default()));
+    }
+
+    #[test]
+    fn compat_rejects_unknown_or_malformed_settings() {
+        assert!(parse_compat("default-width").is_err());
+        assert!(parse_compat("default-width=wide").is_err());
+        assert!(parse_compat("line-width=2").is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    io::Write as _,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+const FORMATTED: &str = "c #ff0000\ndot 0 0\n";
+const UNFORMATTED: &str = "c #f00   dot 0 0";
+const BROKEN: &str = "dot 0 0 @";
+
+/// `EXIT_CODE_FOUND_ERRORS` & `EXIT_CODE_NOT_FORMATTED` of the viewer
+const FOUND_ERRORS: i32 = 90;
+const NOT_FORMATTED: i32 = 98;
+
+fn fmt_check(args: &[&str], stdin: &str) -> Option<i32> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rotur-icn-viewer"))
+        .args(["fmt", "--check"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("the viewer should start");
+
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(stdin.as_bytes())
+        .expect("the ICN should be written to stdin");
+
+    child.wait().expect("the viewer should finish").code()
+}
+
+/// Write the ICNs into a fresh directory, returning their paths
+fn write_icons(dir: &str, icons: &[&str]) -> Vec<PathBuf> {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(dir);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("the directory should be created");
+
+    icons
+        .iter()
+        .enumerate()
+        .map(|(i, src)| {
+            let path = dir.join(format!("{i}.icn"));
+            std::fs::write(&path, src).expect("the ICN should be written");
+            path
+        })
+        .collect()
+}
+
+fn fmt_check_files(dir: &str, icons: &[&str]) -> Option<i32> {
+    let paths = write_icons(dir, icons);
+    let args = paths
+        .iter()
+        .map(|path| path.to_str().expect("the path is unicode"))
+        .collect::<Vec<_>>();
+
+    let code = fmt_check(&args, "");
+
+    for (path, src) in paths.iter().zip(icons) {
+        assert_eq!(
+            std::fs::read_to_string(path).ok().as_deref(),
+            Some(*src),
+            "checking shouldn't write to {}",
+            path.display()
+        );
+    }
+
+    code
+}
+
+#[test]
+fn check_of_stdin_exits_with_whether_its_formatted() {
+    assert_eq!(fmt_check(&[], FORMATTED), Some(0));
+    assert_eq!(fmt_check(&[], UNFORMATTED), Some(NOT_FORMATTED));
+    assert_eq!(fmt_check(&[], BROKEN), Some(FOUND_ERRORS));
+}
+
+#[test]
+fn check_of_files_exits_with_the_worst_of_them() {
+    assert_eq!(
+        fmt_check_files("fmt-formatted", &[FORMATTED, FORMATTED]),
+        Some(0)
+    );
+    assert_eq!(
+        fmt_check_files("fmt-unformatted", &[FORMATTED, UNFORMATTED]),
+        Some(NOT_FORMATTED)
+    );
+    // errors win over unformatted files
+    assert_eq!(
+        fmt_check_files("fmt-broken", &[BROKEN, UNFORMATTED, FORMATTED]),
+        Some(FOUND_ERRORS)
+    );
+}
@@ -0,0 +1,87 @@
+use js_sys::{JSON, Uint8ClampedArray};
+use rotur_icn_renderer::{cpu::Renderer, fitter};
+use rotur_icn_units::{Colour, Number, Vector};
+use wasm_bindgen::prelude::*;
+
+/// Process the ICN & return its diagnostics
+///
+/// They're an array of objects, as serialised by the pipeline's `Errors::to_json`.
+#[wasm_bindgen]
+pub fn process(src: &str) -> JsValue {
+    let (_, _, _, errors) = rotur_icn_pipeline::process(src);
+
+    JSON::parse(&errors.to_json(src).to_string())
+        .unwrap_or_else(|_| unreachable!("the diagnostics are valid JSON"))
+}
+
+/// Render the ICN into RGBA pixels, ready for an `ImageData`
+///
+/// The canvas is `width`×`height` in ICN units, centred on the `camera` (`[x, y]`),
+/// with `scale` pixels per unit. The background is an RGBA colour, as `0xRRGGBBAA`.
+/// An ICN with errors is rendered as far as it's understood.
+///
+/// # Errors
+///
+/// If the camera isn't made of 2 numbers, a parameter isn't finite
+/// or the canvas is over the renderer's default pixel budget.
+#[wasm_bindgen]
+pub fn render(
+    src: &str,
+    width: Number,
+    height: Number,
+    scale: Number,
+    camera: &[Number],
+    bg: u32,
+) -> Result<Uint8ClampedArray, JsError> {
+    let &[x, y] = camera else {
+        return Err(JsError::new("the camera should be made of 2 numbers"));
+    };
+
+    let (_, _, icon, _) = rotur_icn_pipeline::process(src);
+
+    let mut renderer = Renderer::new(
+        Vector {
+            x: width,
+            y: height,
+        },
+        scale,
+        Vector { x, y },
+        Colour::from_u32_with_alpha(bg),
+    );
+    renderer.load(&icon);
+
+    let (mut buf, _) = renderer.try_new_buf()?;
+    renderer.try_render(&mut buf)?;
+
+    Ok(Uint8ClampedArray::from(buf.as_slice()))
+}
+
+/// The smallest canvas, which fits the whole ICN
+#[wasm_bindgen]
+pub struct Fitted {
+    pub width: Number,
+    pub height: Number,
+    camera: Vector,
+}
+
+#[wasm_bindgen]
+impl Fitted {
+    /// Where the canvas is centred, as `[x, y]`
+    #[wasm_bindgen(getter)]
+    pub fn camera(&self) -> Box<[Number]> {
+        Box::new([self.camera.x, self.camera.y])
+    }
+}
+
+/// Fit the canvas around the ICN, to pass its sizes & camera on to [`render`]
+#[wasm_bindgen]
+pub fn fit(src: &str) -> Fitted {
+    let (_, _, icon, _) = rotur_icn_pipeline::process(src);
+    let fitted = fitter::fit(&icon);
+
+    Fitted {
+        width: fitted.size.x,
+        height: fitted.size.y,
+        camera: fitted.camera,
+    }
+}
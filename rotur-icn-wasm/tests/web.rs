@@ -0,0 +1,63 @@
+//! Run with `cargo test -p rotur-icn-wasm --target wasm32-unknown-unknown`,
+//! which needs `wasm-bindgen-test-runner` (from `wasm-bindgen-cli`) & node
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, Reflect};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &key.into()).expect("the value is an object")
+}
+
+#[wasm_bindgen_test]
+fn process_reports_diagnostics() {
+    let diagnostics = Array::from(&rotur_icn_wasm::process("w 1\nlime 0 0 1 1\n"));
+
+    assert_eq!(diagnostics.length(), 1);
+
+    let diagnostic = diagnostics.get(0);
+    assert_eq!(get(&diagnostic, "stage"), "lowering");
+    assert_eq!(get(&diagnostic, "help"), "did you mean `line`?");
+    assert_eq!(get(&get(&get(&diagnostic, "range"), "start"), "line"), 2);
+}
+
+#[wasm_bindgen_test]
+fn process_valid_icn() {
+    assert_eq!(Array::from(&rotur_icn_wasm::process("dot 0 0")).length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn render_pixels() {
+    let pixels = rotur_icn_wasm::render("c #f00\ndot 0 0", 20., 10., 2., &[0., 0.], 0x0000_ffff)
+        .expect("the camera is valid");
+
+    assert_eq!(pixels.length(), 40 * 20 * 4);
+
+    // the centre is covered by the dot, the corner is left to the background
+    let centre = (10 * 40 + 20) * 4;
+    assert_eq!(pixels.to_vec()[centre..centre + 4], [0xff, 0, 0, 0xff]);
+    assert_eq!(pixels.to_vec()[..4], [0, 0, 0xff, 0xff]);
+}
+
+#[wasm_bindgen_test]
+fn render_invalid_camera() {
+    assert!(rotur_icn_wasm::render("dot 0 0", 20., 20., 1., &[0.], 0).is_err());
+}
+
+#[wasm_bindgen_test]
+fn render_too_large_canvas() {
+    assert!(rotur_icn_wasm::render("dot 0 0", 20., 20., 1e6, &[0., 0.], 0).is_err());
+    assert!(rotur_icn_wasm::render("dot 0 0", f32::NAN, 20., 1., &[0., 0.], 0).is_err());
+}
+
+#[wasm_bindgen_test]
+#[expect(clippy::float_cmp, reason = "the bounds are exact")]
+fn fit_around_icn() {
+    let fitted = rotur_icn_wasm::fit("w 2\nline 0 0 4 0");
+
+    assert_eq!(fitted.width, 6.);
+    assert_eq!(fitted.height, 2.);
+    assert_eq!(*fitted.camera(), [2., 0.]);
+}